@@ -222,6 +222,7 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		assets: Default::default(),
 	}
 }
 
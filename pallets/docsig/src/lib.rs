@@ -160,21 +160,8 @@ pub mod pallet {
 		pub fn new_document(origin: OriginFor<T>, id: u32, document: Vec<u8>) -> DispatchResult {
 			// check the request is signed
 			let sender = ensure_signed(origin)?;
-			//check document length
-			ensure!(document.len() >= 32, Error::<T>::DocumentTooShort);
-			ensure!(document.len() <= 128, Error::<T>::DocumentTooLong);
-			ensure!(id > 0, Error::<T>::IdCannotBeZero);
-			ensure!(!Documents::<T>::contains_key(&sender, id), Error::<T>::DocumentAlreadyPresent);
-			// Insert new Document
-			Documents::<T>::insert(sender.clone(), id, document.clone());
-			// Generate event
-			Self::deposit_event(Event::DocumentCreated {
-				account: sender,
-				documentid: id,
-				documenthash: document,
-			});
-			// Return a successful DispatchResult
-			Ok(())
+			// store the document
+			Self::do_new_document(sender, id, document)
 		}
 		/// Destroy a Document
 		#[pallet::call_index(2)]
@@ -286,4 +273,26 @@ pub mod pallet {
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Store a new document of the account, it's used from the extrinsic and from the other
+		/// pallets anchoring their documents (for example the invoices of the market place)
+		pub fn do_new_document(sender: T::AccountId, id: u32, document: Vec<u8>) -> DispatchResult {
+			//check document length
+			ensure!(document.len() >= 32, Error::<T>::DocumentTooShort);
+			ensure!(document.len() <= 128, Error::<T>::DocumentTooLong);
+			ensure!(id > 0, Error::<T>::IdCannotBeZero);
+			ensure!(!Documents::<T>::contains_key(&sender, id), Error::<T>::DocumentAlreadyPresent);
+			// Insert new Document
+			Documents::<T>::insert(sender.clone(), id, document.clone());
+			// Generate event
+			Self::deposit_event(Event::DocumentCreated {
+				account: sender,
+				documentid: id,
+				documenthash: document,
+			});
+			// Return a successful DispatchResult
+			Ok(())
+		}
+	}
 }
@@ -0,0 +1,54 @@
+//! Runtime API definition for the market place pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_marketplace::{
+	Balance, CurrencyCode, CurrencyInfo, ProductFilter, ProductInfo, ProductStatus, SellerInfo,
+	SellerVerification, ShippingRateTable, TaxQuote,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Catalogue queries of the market place, the records are returned as stored
+	#[api_version(7)]
+	pub trait MarketplaceApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Get the seller data of an account
+		fn seller(account: AccountId) -> Option<SellerInfo<AccountId>>;
+		/// Get a page of active products (uid, data) matching the filter, starting from the
+		/// `start` position
+		fn products(
+			filter: ProductFilter<AccountId>,
+			start: u32,
+			limit: u32,
+		) -> Vec<(u32, ProductInfo)>;
+		/// Get the shipping rates (uid, data) of a shipper
+		fn shipping_rates(shipper: u32) -> Vec<(u32, ShippingRateTable)>;
+		/// Get all the currencies (code, data)
+		fn currencies() -> Vec<(CurrencyCode, CurrencyInfo)>;
+		/// Get the cheapest shipping quote (shipper, currency, amount) of every shipper of a
+		/// product for the quantity and the destination country
+		fn quote_shipping(
+			uid: u32,
+			destination: Vec<u8>,
+			quantity: u32,
+		) -> Vec<(u32, Vec<u8>, Balance)>;
+		/// Get the tax line (price, rate, tax, total) of a product shipped to the destination
+		/// country
+		fn quote_tax(uid: u32, destination: Vec<u8>) -> Option<TaxQuote>;
+		/// Check the seller delivers to the GPS coordinates in microdegrees (degrees * 1_000_000)
+		fn seller_ships_to(account: AccountId, latitude: i32, longitude: i32) -> bool;
+		/// Check the product is delivered to the GPS coordinates in microdegrees, from its areas
+		/// or from the areas of its seller
+		fn product_ships_to(uid: u32, latitude: i32, longitude: i32) -> bool;
+		/// Get the uids of the products with the barcode (GTIN-8, UPC-A, EAN-13 or GTIN-14)
+		fn products_by_gtin(code: Vec<u8>) -> Vec<u32>;
+		/// Get the KYC level and the certifications verified of a seller
+		fn seller_verification(account: AccountId) -> Option<SellerVerification>;
+		/// Get the status of a product, the drafts and the products paused or discontinued are
+		/// not listed
+		fn product_status(uid: u32) -> Option<ProductStatus>;
+	}
+}
@@ -0,0 +1,311 @@
+//! RPC interface for the market place pallet.
+//! The methods query the catalogue through the runtime api, so the clients don't need to
+//! decode the storage keys. The records are returned SCALE encoded, the clients decode them
+//! with the types of the runtime metadata.
+
+use std::sync::Arc;
+
+use codec::{Codec, Encode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_marketplace_rpc_runtime_api::MarketplaceApi as MarketplaceRuntimeApi;
+use pallet_marketplace_rpc_runtime_api::ProductFilter;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Market place RPC methods.
+#[rpc(client, server)]
+pub trait MarketplaceApi<BlockHash, AccountId> {
+	/// Get the seller data of an account
+	#[method(name = "marketplace_getSeller")]
+	fn get_seller(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<Bytes>>;
+
+	/// Get a page of active products (uid, data) with optional filters for seller, (department,
+	/// category) and currency code
+	#[method(name = "marketplace_getProducts")]
+	fn get_products(
+		&self,
+		seller: Option<AccountId>,
+		category: Option<(u32, u32)>,
+		currency: Option<String>,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u32, Bytes)>>;
+
+	/// Get the shipping rates (uid, data) of a shipper
+	#[method(name = "marketplace_getShippingRates")]
+	fn get_shipping_rates(
+		&self,
+		shipper: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u32, Bytes)>>;
+
+	/// Get all the currencies (code, data)
+	#[method(name = "marketplace_getCurrencies")]
+	fn get_currencies(&self, at: Option<BlockHash>) -> RpcResult<Vec<(String, Bytes)>>;
+
+	/// Get the cheapest shipping quote (shipper, currency, amount) of every shipper of a
+	/// product for the quantity and the destination country
+	#[method(name = "marketplace_quoteShipping")]
+	fn quote_shipping(
+		&self,
+		uid: u32,
+		destination: String,
+		quantity: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u32, String, u128)>>;
+
+	/// Get the tax line (price, rate, tax, total) of a product shipped to the destination
+	/// country
+	#[method(name = "marketplace_quoteTax")]
+	fn quote_tax(
+		&self,
+		uid: u32,
+		destination: String,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Check the seller delivers to the GPS coordinates in microdegrees (degrees * 1_000_000)
+	#[method(name = "marketplace_sellerShipsTo")]
+	fn seller_ships_to(
+		&self,
+		account: AccountId,
+		latitude: i32,
+		longitude: i32,
+		at: Option<BlockHash>,
+	) -> RpcResult<bool>;
+
+	/// Check the product is delivered to the GPS coordinates in microdegrees, from its areas or
+	/// from the areas of its seller
+	#[method(name = "marketplace_productShipsTo")]
+	fn product_ships_to(
+		&self,
+		uid: u32,
+		latitude: i32,
+		longitude: i32,
+		at: Option<BlockHash>,
+	) -> RpcResult<bool>;
+
+	/// Get the uids of the products with the barcode (GTIN-8, UPC-A, EAN-13 or GTIN-14)
+	#[method(name = "marketplace_getProductsByGtin")]
+	fn get_products_by_gtin(&self, code: String, at: Option<BlockHash>) -> RpcResult<Vec<u32>>;
+
+	/// Get the KYC level and the certifications verified of a seller, for the verified seller
+	/// badge
+	#[method(name = "marketplace_getSellerVerification")]
+	fn get_seller_verification(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Get the status of a product (draft, active, paused or discontinued)
+	#[method(name = "marketplace_getProductStatus")]
+	fn get_product_status(&self, uid: u32, at: Option<BlockHash>) -> RpcResult<Option<Bytes>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Provides the RPC methods to query the market place.
+pub struct Marketplace<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Marketplace<C, Block> {
+	/// Create new `Marketplace` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+// convert a code stored on chain to a string
+fn to_string(data: Vec<u8>) -> String {
+	String::from_utf8_lossy(&data).into_owned()
+}
+
+// encode a record stored on chain
+fn to_bytes(data: impl Encode) -> Bytes {
+	Bytes(data.encode())
+}
+
+// map an error of the runtime api call
+fn runtime_error(error: impl ToString) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the market place.",
+		Some(error.to_string()),
+	))
+}
+
+impl<C, Block, AccountId> MarketplaceApiServer<<Block as BlockT>::Hash, AccountId>
+	for Marketplace<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MarketplaceRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn get_seller(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let seller = api.seller(at, account).map_err(runtime_error)?;
+		Ok(seller.map(to_bytes))
+	}
+
+	fn get_products(
+		&self,
+		seller: Option<AccountId>,
+		category: Option<(u32, u32)>,
+		currency: Option<String>,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u32, Bytes)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		// a currency code too long cannot match any product
+		let currency = match currency.map(|c| c.into_bytes().try_into()).transpose() {
+			Ok(currency) => currency,
+			Err(_) => return Ok(Vec::new()),
+		};
+		let filter = ProductFilter { seller, category, currency };
+		let products = api.products(at, filter, start, limit).map_err(runtime_error)?;
+		Ok(products.into_iter().map(|(uid, data)| (uid, to_bytes(data))).collect())
+	}
+
+	fn get_shipping_rates(
+		&self,
+		shipper: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u32, Bytes)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let rates = api.shipping_rates(at, shipper).map_err(runtime_error)?;
+		Ok(rates.into_iter().map(|(uid, data)| (uid, to_bytes(data))).collect())
+	}
+
+	fn get_currencies(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(String, Bytes)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let currencies = api.currencies(at).map_err(runtime_error)?;
+		Ok(currencies
+			.into_iter()
+			.map(|(code, data)| (to_string(code.into_inner()), to_bytes(data)))
+			.collect())
+	}
+
+	fn quote_shipping(
+		&self,
+		uid: u32,
+		destination: String,
+		quantity: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u32, String, u128)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let quotes = api
+			.quote_shipping(at, uid, destination.into_bytes(), quantity)
+			.map_err(runtime_error)?;
+		Ok(quotes
+			.into_iter()
+			.map(|(shipper, currency, amount)| (shipper, to_string(currency), amount))
+			.collect())
+	}
+
+	fn quote_tax(
+		&self,
+		uid: u32,
+		destination: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let quote = api.quote_tax(at, uid, destination.into_bytes()).map_err(runtime_error)?;
+		Ok(quote.map(to_bytes))
+	}
+
+	fn seller_ships_to(
+		&self,
+		account: AccountId,
+		latitude: i32,
+		longitude: i32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<bool> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let ships = api.seller_ships_to(at, account, latitude, longitude).map_err(runtime_error)?;
+		Ok(ships)
+	}
+
+	fn product_ships_to(
+		&self,
+		uid: u32,
+		latitude: i32,
+		longitude: i32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<bool> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let ships = api.product_ships_to(at, uid, latitude, longitude).map_err(runtime_error)?;
+		Ok(ships)
+	}
+
+	fn get_products_by_gtin(
+		&self,
+		code: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<u32>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let products = api.products_by_gtin(at, code.into_bytes()).map_err(runtime_error)?;
+		Ok(products)
+	}
+
+	fn get_seller_verification(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let verification = api.seller_verification(at, account).map_err(runtime_error)?;
+		Ok(verification.map(to_bytes))
+	}
+
+	fn get_product_status(
+		&self,
+		uid: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let status = api.product_status(at, uid).map_err(runtime_error)?;
+		Ok(status.map(to_bytes))
+	}
+}
@@ -14,6 +14,7 @@ mod benchmarks {
 	#[benchmark]
 	fn create_iso_country() {
 		let caller: T::AccountId = whitelisted_caller();
+		Curators::<T>::insert(&caller, CuratorDomain::Countries, ());
 		let mut countrycode = Vec::<u8>::new();
 		countrycode.push(b'A');
 		countrycode.push(b'I');
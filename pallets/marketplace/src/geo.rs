@@ -0,0 +1,89 @@
+//! GPS coordinates for no/std without floating point arithmetic.
+//! The coordinates are stored in microdegrees (degrees * 1_000_000) and the distances are
+//! compared through the haversine of the central angle between two points, computed in fixed
+//! point. The haversine grows with the distance so the comparison doesn't need the square root
+//! and the arcsine of the full formula.
+
+/// Microdegrees in a degree
+pub const MICRODEGREES: i32 = 1_000_000;
+/// Maximum absolute latitude in microdegrees
+pub const MAX_LATITUDE: i32 = 90 * MICRODEGREES;
+/// Maximum absolute longitude in microdegrees
+pub const MAX_LONGITUDE: i32 = 180 * MICRODEGREES;
+
+// scale of the fixed point numbers used for the trigonometry
+const SCALE: i128 = 1_000_000_000_000;
+// pi in fixed point
+const PI: i128 = 3_141_592_653_590;
+
+/// Check the latitude is between -90 and 90 degrees
+pub fn valid_latitude(latitude: i32) -> bool {
+	(-MAX_LATITUDE..=MAX_LATITUDE).contains(&latitude)
+}
+
+/// Check the longitude is between -180 and 180 degrees
+pub fn valid_longitude(longitude: i32) -> bool {
+	(-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&longitude)
+}
+
+/// Parse a coordinate in decimal degrees (for example "45.464211" or "-9.19") to microdegrees,
+/// the digits after the sixth decimal are truncated. None when the text is not a decimal number
+/// with at most 3 integer digits, the range is checked from the caller.
+pub fn parse_coordinate(text: &[u8]) -> Option<i32> {
+	let (negative, digits) = match text.split_first() {
+		Some((b'-', digits)) => (true, digits),
+		Some((b'+', digits)) => (false, digits),
+		_ => (false, text),
+	};
+	let mut parts = digits.splitn(2, |c| *c == b'.');
+	let integer = parts.next().unwrap_or_default();
+	let decimals = parts.next().unwrap_or_default();
+	if (integer.is_empty() && decimals.is_empty()) || integer.len() > 3 {
+		return None
+	}
+	if !integer.iter().chain(decimals.iter()).all(u8::is_ascii_digit) {
+		return None
+	}
+	let mut value: i32 = integer.iter().fold(0, |v, c| v * 10 + (c - b'0') as i32) * MICRODEGREES;
+	let mut unit = MICRODEGREES;
+	for c in decimals.iter().take(6) {
+		unit /= 10;
+		value += (c - b'0') as i32 * unit;
+	}
+	Some(if negative { -value } else { value })
+}
+
+/// Haversine of the central angle between two points (microdegrees), in fixed point where
+/// 1_000_000_000_000 is 1. It's 0 for the same point and 1 for antipodal points.
+pub fn haversine(latitude1: i32, longitude1: i32, latitude2: i32, longitude2: i32) -> i128 {
+	let phi1 = radians(latitude1);
+	let phi2 = radians(latitude2);
+	let deltalambda = radians(longitude2) - radians(longitude1);
+	// hav(x) = (1 - cos(x)) / 2
+	let havphi = (SCALE - cos(phi2 - phi1)) / 2;
+	let havlambda = (SCALE - cos(deltalambda)) / 2;
+	havphi + cos(phi1) * cos(phi2) / SCALE * havlambda / SCALE
+}
+
+// convert microdegrees to radians in fixed point
+fn radians(microdegrees: i32) -> i128 {
+	microdegrees as i128 * PI / (180 * MICRODEGREES as i128)
+}
+
+// cosine in fixed point from the Taylor series, the angle is reduced to 0..pi first
+fn cos(angle: i128) -> i128 {
+	let mut x = angle.abs() % (2 * PI);
+	if x > PI {
+		x = 2 * PI - x;
+	}
+	let x2 = x * x / SCALE;
+	let mut term = SCALE;
+	let mut sum = SCALE;
+	let mut n: i128 = 0;
+	while term != 0 {
+		n += 2;
+		term = -term * x2 / SCALE / (n * (n - 1));
+		sum += term;
+	}
+	sum
+}
@@ -0,0 +1,293 @@
+//! Json parser for no/std used to validate and read the configurations of the market place.
+//! The length of the document and the nesting depth are bounded, the strings are unescaped and
+//! the fields are read only at the level of the object requested.
+
+use frame_support::RuntimeDebug;
+use scale_info::prelude::vec::Vec;
+
+/// Maximum length in bytes of a json document
+pub const MAX_JSON_LENGTH: usize = 65536;
+/// Maximum nesting depth of arrays and objects
+pub const MAX_JSON_DEPTH: usize = 8;
+
+/// Errors of the json parser, the position is the offset in bytes of the wrong character
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum JsonError {
+	/// The document is longer than MAX_JSON_LENGTH
+	TooLong,
+	/// Arrays and objects are nested deeper than MAX_JSON_DEPTH
+	TooDeep,
+	/// The document ended before the value was complete
+	UnexpectedEnd,
+	/// A character not allowed at the position
+	UnexpectedCharacter(usize),
+	/// A number is not well formed
+	InvalidNumber(usize),
+	/// A string has a wrong escape sequence, a control character or it's not utf8
+	InvalidString(usize),
+	/// The same key is present twice in an object
+	DuplicateKey(usize),
+	/// There are characters after the end of the document
+	TrailingCharacters(usize),
+}
+
+/// A json value, the numbers are kept as written in the document
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Default)]
+pub enum JsonValue {
+	#[default]
+	Null,
+	Bool(bool),
+	Number(Vec<u8>),
+	String(Vec<u8>),
+	Array(Vec<JsonValue>),
+	Object(Vec<(Vec<u8>, JsonValue)>),
+}
+
+impl JsonValue {
+	/// Get a field of an object, None when the field is missing or the value is not an object
+	pub fn get(&self, key: &str) -> Option<&JsonValue> {
+		match self {
+			JsonValue::Object(fields) =>
+				fields.iter().find(|(k, _)| k.as_slice() == key.as_bytes()).map(|(_, v)| v),
+			_ => None,
+		}
+	}
+	/// Get a field as bytes (see `to_bytes`), empty when the field is missing
+	pub fn get_bytes(&self, key: &str) -> Vec<u8> {
+		self.get(key).map(|v| v.to_bytes()).unwrap_or_default()
+	}
+	/// Get the items of an array field, empty when the field is missing or it's not an array
+	pub fn get_array(&self, key: &str) -> &[JsonValue] {
+		match self.get(key) {
+			Some(JsonValue::Array(items)) => items,
+			_ => &[],
+		}
+	}
+	/// Get the value as bytes: the unescaped string, the number or the boolean as written and
+	/// empty for null, arrays and objects
+	pub fn to_bytes(&self) -> Vec<u8> {
+		match self {
+			JsonValue::String(s) | JsonValue::Number(s) => s.clone(),
+			JsonValue::Bool(true) => b"true".to_vec(),
+			JsonValue::Bool(false) => b"false".to_vec(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// Parse a json document
+pub fn parse(input: &[u8]) -> Result<JsonValue, JsonError> {
+	if input.len() > MAX_JSON_LENGTH {
+		return Err(JsonError::TooLong)
+	}
+	let mut parser = Parser { input, pos: 0 };
+	parser.skip_whitespaces();
+	let value = parser.value(0)?;
+	parser.skip_whitespaces();
+	if parser.pos < input.len() {
+		return Err(JsonError::TrailingCharacters(parser.pos))
+	}
+	Ok(value)
+}
+
+struct Parser<'a> {
+	input: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn peek(&self) -> Option<u8> {
+		self.input.get(self.pos).copied()
+	}
+	fn next(&mut self) -> Result<u8, JsonError> {
+		let c = self.peek().ok_or(JsonError::UnexpectedEnd)?;
+		self.pos += 1;
+		Ok(c)
+	}
+	fn expect(&mut self, c: u8) -> Result<(), JsonError> {
+		let pos = self.pos;
+		if self.next()? != c {
+			return Err(JsonError::UnexpectedCharacter(pos))
+		}
+		Ok(())
+	}
+	fn skip_whitespaces(&mut self) {
+		while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+			self.pos += 1;
+		}
+	}
+	fn value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+		match self.peek().ok_or(JsonError::UnexpectedEnd)? {
+			b'{' => self.object(depth + 1),
+			b'[' => self.array(depth + 1),
+			b'"' => Ok(JsonValue::String(self.string()?)),
+			b't' => self.literal(b"true", JsonValue::Bool(true)),
+			b'f' => self.literal(b"false", JsonValue::Bool(false)),
+			b'n' => self.literal(b"null", JsonValue::Null),
+			b'-' | b'0'..=b'9' => self.number(),
+			_ => Err(JsonError::UnexpectedCharacter(self.pos)),
+		}
+	}
+	fn literal(&mut self, literal: &[u8], value: JsonValue) -> Result<JsonValue, JsonError> {
+		for c in literal {
+			self.expect(*c)?;
+		}
+		Ok(value)
+	}
+	fn object(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+		if depth > MAX_JSON_DEPTH {
+			return Err(JsonError::TooDeep)
+		}
+		self.expect(b'{')?;
+		let mut fields: Vec<(Vec<u8>, JsonValue)> = Vec::new();
+		self.skip_whitespaces();
+		if self.peek() == Some(b'}') {
+			self.pos += 1;
+			return Ok(JsonValue::Object(fields))
+		}
+		loop {
+			self.skip_whitespaces();
+			let keypos = self.pos;
+			let key = self.string()?;
+			if fields.iter().any(|(k, _)| *k == key) {
+				return Err(JsonError::DuplicateKey(keypos))
+			}
+			self.skip_whitespaces();
+			self.expect(b':')?;
+			self.skip_whitespaces();
+			let value = self.value(depth)?;
+			fields.push((key, value));
+			self.skip_whitespaces();
+			let pos = self.pos;
+			match self.next()? {
+				b',' => continue,
+				b'}' => return Ok(JsonValue::Object(fields)),
+				_ => return Err(JsonError::UnexpectedCharacter(pos)),
+			}
+		}
+	}
+	fn array(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+		if depth > MAX_JSON_DEPTH {
+			return Err(JsonError::TooDeep)
+		}
+		self.expect(b'[')?;
+		let mut items: Vec<JsonValue> = Vec::new();
+		self.skip_whitespaces();
+		if self.peek() == Some(b']') {
+			self.pos += 1;
+			return Ok(JsonValue::Array(items))
+		}
+		loop {
+			self.skip_whitespaces();
+			items.push(self.value(depth)?);
+			self.skip_whitespaces();
+			let pos = self.pos;
+			match self.next()? {
+				b',' => continue,
+				b']' => return Ok(JsonValue::Array(items)),
+				_ => return Err(JsonError::UnexpectedCharacter(pos)),
+			}
+		}
+	}
+	fn number(&mut self) -> Result<JsonValue, JsonError> {
+		let start = self.pos;
+		if self.peek() == Some(b'-') {
+			self.pos += 1;
+		}
+		// integer part without leading zeros
+		match self.peek() {
+			Some(b'0') => self.pos += 1,
+			Some(b'1'..=b'9') => self.digits(),
+			_ => return Err(JsonError::InvalidNumber(start)),
+		}
+		// fraction
+		if self.peek() == Some(b'.') {
+			self.pos += 1;
+			if !matches!(self.peek(), Some(b'0'..=b'9')) {
+				return Err(JsonError::InvalidNumber(start))
+			}
+			self.digits();
+		}
+		// exponent
+		if let Some(b'e' | b'E') = self.peek() {
+			self.pos += 1;
+			if let Some(b'+' | b'-') = self.peek() {
+				self.pos += 1;
+			}
+			if !matches!(self.peek(), Some(b'0'..=b'9')) {
+				return Err(JsonError::InvalidNumber(start))
+			}
+			self.digits();
+		}
+		Ok(JsonValue::Number(self.input[start..self.pos].to_vec()))
+	}
+	fn digits(&mut self) {
+		while let Some(b'0'..=b'9') = self.peek() {
+			self.pos += 1;
+		}
+	}
+	fn string(&mut self) -> Result<Vec<u8>, JsonError> {
+		let start = self.pos;
+		self.expect(b'"')?;
+		let mut result = Vec::new();
+		loop {
+			let pos = self.pos;
+			match self.next()? {
+				b'"' => break,
+				b'\\' => match self.next()? {
+					b'"' => result.push(b'"'),
+					b'\\' => result.push(b'\\'),
+					b'/' => result.push(b'/'),
+					b'b' => result.push(0x08),
+					b'f' => result.push(0x0c),
+					b'n' => result.push(b'\n'),
+					b'r' => result.push(b'\r'),
+					b't' => result.push(b'\t'),
+					b'u' => {
+						let c = self.unicode_escape(pos)?;
+						let mut buffer = [0u8; 4];
+						result.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+					},
+					_ => return Err(JsonError::InvalidString(pos)),
+				},
+				c if c < 0x20 => return Err(JsonError::InvalidString(pos)),
+				c => result.push(c),
+			}
+		}
+		if core::str::from_utf8(&result).is_err() {
+			return Err(JsonError::InvalidString(start))
+		}
+		Ok(result)
+	}
+	// decode the \uXXXX escape (the \u is already consumed), with the surrogate pairs
+	fn unicode_escape(&mut self, pos: usize) -> Result<char, JsonError> {
+		let high = self.hex4(pos)?;
+		let code = if (0xD800..0xDC00).contains(&high) {
+			// a low surrogate must follow
+			if self.next()? != b'\\' || self.next()? != b'u' {
+				return Err(JsonError::InvalidString(pos))
+			}
+			let low = self.hex4(pos)?;
+			if !(0xDC00..0xE000).contains(&low) {
+				return Err(JsonError::InvalidString(pos))
+			}
+			0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+		} else {
+			high
+		};
+		char::from_u32(code).ok_or(JsonError::InvalidString(pos))
+	}
+	fn hex4(&mut self, pos: usize) -> Result<u32, JsonError> {
+		let mut value = 0u32;
+		for _ in 0..4 {
+			let digit = match self.next()? {
+				c @ b'0'..=b'9' => c - b'0',
+				c @ b'a'..=b'f' => c - b'a' + 10,
+				c @ b'A'..=b'F' => c - b'A' + 10,
+				_ => return Err(JsonError::InvalidString(pos)),
+			};
+			value = value * 16 + digit as u32;
+		}
+		Ok(value)
+	}
+}
@@ -3,8 +3,8 @@
 pub use core::{str, str::FromStr};
 /// Pallet to manage the state of the market place
 pub use pallet::*;
-use scale_info::prelude::vec;
 pub use scale_info::prelude::vec::Vec;
+use scale_info::prelude::{boxed::Box, collections::BTreeSet, vec};
 
 #[cfg(test)]
 mod mock;
@@ -14,18 +14,39 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod geo;
+pub mod json;
+pub mod migrations;
+pub mod types;
 pub mod weights;
 pub use bs58;
+pub use types::*;
 pub use weights::*;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::{
+			traits::{Hash, One, SaturatedConversion, Saturating, Zero},
+			Percent, Permill,
+		},
+		storage::with_storage_layer,
+		traits::{
+			fungibles, tokens::Preservation, BalanceStatus, Currency, ExistenceRequirement,
+			ReservableCurrency,
+		},
+	};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version, 1 stores the typed records in place of the json, 2 counts the
+	/// references to the reference data, 3 stores the GPS coordinates in microdegrees, 4 indexes
+	/// the products by GTIN, 5 indexes the products by seller, category and currency
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -35,92 +56,743 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// The currency used to pay the orders, the payment is reserved from the buyer until the
+		/// delivery has been confirmed
+		type Currency: ReservableCurrency<Self::AccountId, Balance = Balance>;
+		/// The origin allowed to resolve the disputes of the escrows
+		type ArbitratorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The origin allowed to manage the curators, it can also maintain all the reference data
+		type CuratorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The deposit reserved for each seller or product stored
+		#[pallet::constant]
+		type ItemDeposit: Get<Balance>;
+		/// The deposit reserved for each byte of the seller or product data stored
+		#[pallet::constant]
+		type ByteDeposit: Get<Balance>;
+		/// Identifier of the assets settling the non native currencies
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+		/// The assets used to pay the products priced in a currency mapped to an asset
+		type Assets: fungibles::Mutate<Self::AccountId, AssetId = Self::AssetId, Balance = Balance>;
+		/// The origin allowed to manage the tax rates
+		type TaxOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Anchor of the hashes of the invoices, the parties can co-sign them
+		type DocumentAnchor: DocumentAnchor<Self::AccountId>;
+		/// The origin allowed to set the resellers, they can register products with the GTIN of
+		/// products of other sellers
+		type ResellerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of blocks in a day, used to convert the return policy of the sellers in blocks
+		#[pallet::constant]
+		type BlocksPerDay: Get<Self::BlockNumber>;
+		/// Maximum number of products stored with a single batch_upsert_products
+		#[pallet::constant]
+		type MaxProductsBatch: Get<u32>;
+		/// Maximum weight of the settlements of the auctions and the charges of the subscriptions
+		/// at the beginning of a block, the ones exceeding it are delayed to the following blocks
+		#[pallet::constant]
+		type MaxInitializeWeight: Get<Weight>;
+	}
+
+	/// Anchor of the hash of a document, it's implemented from a document signing pallet (for
+	/// example pallet-docsig) without coupling the market place to it
+	pub trait DocumentAnchor<AccountId> {
+		/// Store the hash of the document `id` of the owner
+		fn anchor(owner: &AccountId, id: u32, hash: Vec<u8>) -> DispatchResult;
+	}
+
+	impl<AccountId> DocumentAnchor<AccountId> for () {
+		fn anchor(_owner: &AccountId, _id: u32, _hash: Vec<u8>) -> DispatchResult {
+			Ok(())
+		}
 	}
 	pub type Balance = u128;
 
+	/// Maximum number of references of a product to the reference data: currency, category,
+	/// media colors (16), colors (32), sizes (32), shipping countries (256) and shippers (16)
+	pub const MAX_PRODUCT_REFERENCES: u32 = 354;
+	/// Maximum number of references of a seller to the reference data: categories (32) and
+	/// countries (256)
+	pub const MAX_SELLER_REFERENCES: u32 = 32 + 256;
+	/// Maximum number of references of a shipper to the reference data: origin and destination
+	/// countries (256 each)
+	pub const MAX_SHIPPER_REFERENCES: u32 = 256 + 256;
+	/// Maximum number of references of the shipping rates to the reference data: shipper, origin
+	/// country, currency and destination countries (256)
+	pub const MAX_SHIPPING_RATE_REFERENCES: u32 = 3 + 256;
+	/// Maximum number of stock variants of a product, one for each color and size
+	pub const MAX_STOCK_VARIANTS: u32 = 32 * 32;
+
+	/// Status of an order, it can move only forward: Placed -> Shipped -> Delivered -> Returned
+	/// or Placed -> Cancelled
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum OrderStatus {
+		Placed,
+		Shipped,
+		Delivered,
+		Cancelled,
+		Returned,
+	}
+
+	/// Order of a product, the amount is reserved on the buyer account till the delivery
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Order<AccountId, BlockNumber> {
+		/// account paying the order
+		pub buyer: AccountId,
+		/// account receiving the payment on delivery
+		pub seller: AccountId,
+		/// uid of the product ordered
+		pub product: u32,
+		/// number of items ordered
+		pub quantity: u32,
+		/// total amount reserved from the buyer
+		pub amount: Balance,
+		/// current status of the order
+		pub status: OrderStatus,
+		/// block when the order has been placed
+		pub placedat: BlockNumber,
+		/// block when the delivery has been confirmed
+		pub deliveredat: Option<BlockNumber>,
+	}
+	pub type OrderOf<T> =
+		Order<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Status of the return window of a delivered order
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ReturnStatus {
+		/// the buyer can request the return till the window closes
+		Open,
+		/// the buyer has requested the return, the seller must approve or reject it till the
+		/// response deadline, then the buyer can claim the refund
+		Requested,
+		/// the seller has rejected the return
+		Rejected,
+	}
+
+	/// Return window of a delivered order from the return policy of the seller, the payment
+	/// stays reserved on the seller account till the window closes or the return is approved
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ReturnWindow<BlockNumber> {
+		/// last block when the return can be requested
+		pub closesat: BlockNumber,
+		pub status: ReturnStatus,
+		/// last block when the seller can reject the return requested
+		pub respondby: Option<BlockNumber>,
+	}
+	pub type ReturnWindowOf<T> = ReturnWindow<<T as frame_system::Config>::BlockNumber>;
+
+	/// Days for the seller to respond to a return requested, the refund can then be claimed
+	pub const RETURN_RESPONSE_DAYS: u32 = 14;
+
+	pub type SellerInfoOf<T> = SellerInfo<<T as frame_system::Config>::AccountId>;
+
+	/// Filter of the products used from the runtime api, the filters set are all matched
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ProductFilter<AccountId> {
+		/// products of the seller account
+		pub seller: Option<AccountId>,
+		/// products of the (department, category)
+		pub category: Option<(u32, u32)>,
+		/// products priced in the currency code
+		pub currency: Option<CurrencyCode>,
+	}
+
+	/// Maximum number of products returned from a single query
+	pub const MAX_PRODUCTS_PAGE: u32 = 100;
+
+	/// Status of an escrow, it can move only forward: Open -> Released or Open -> Disputed ->
+	/// Resolved
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum EscrowStatus {
+		Open,
+		Released,
+		Disputed,
+		Resolved,
+	}
+
+	/// Escrow of the price of a product, the amount is reserved on the buyer account till it's
+	/// released to the seller or the dispute is resolved from the arbitrators
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Escrow<AccountId, BlockNumber> {
+		/// account paying the product
+		pub buyer: AccountId,
+		/// account receiving the payment
+		pub seller: AccountId,
+		/// uid of the product
+		pub product: u32,
+		/// amount reserved from the buyer
+		pub amount: Balance,
+		/// current status of the escrow
+		pub status: EscrowStatus,
+		/// block when the escrow has been opened
+		pub openedat: BlockNumber,
+	}
+	pub type EscrowOf<T> =
+		Escrow<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Evidence submitted from the buyer or the seller in a dispute
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Evidence<AccountId, BlockNumber> {
+		/// account submitting the evidence
+		pub submitter: AccountId,
+		/// hash of the evidence document
+		pub hash: ShortText,
+		/// optional ipfs address of the evidence document
+		pub ipfs: Option<Text>,
+		/// block when the evidence has been submitted
+		pub submittedat: BlockNumber,
+	}
+	pub type EvidenceOf<T> =
+		Evidence<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Review of a product, it can be left only from a buyer holding a purchase receipt
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Review<AccountId, BlockNumber> {
+		/// seller of the product rated
+		pub seller: AccountId,
+		/// rating from 1 to 5
+		pub rating: u8,
+		/// optional hash of the review text stored off-chain
+		pub reviewhash: Option<ShortText>,
+		/// block when the review has been submitted or updated
+		pub reviewedat: BlockNumber,
+	}
+	pub type ReviewOf<T> =
+		Review<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Running aggregate of the ratings, the average rating is sum/count
+	#[derive(
+		Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct RatingSummary {
+		/// number of ratings received
+		pub count: u32,
+		/// sum of the ratings received
+		pub sum: u32,
+	}
+
+	/// Type of auction with its prices
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum AuctionType {
+		/// the highest bid not lower than the reserve price wins at the end block
+		English { reserveprice: Balance },
+		/// the price decreases every block from the start price to the floor price, the first
+		/// bid wins
+		Dutch { startprice: Balance, decrement: Balance, floorprice: Balance },
+	}
+
+	/// Auction of a product, the highest bid is reserved from the bidder till the settlement
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Auction<AccountId, BlockNumber> {
+		/// account receiving the winning bid
+		pub seller: AccountId,
+		/// uid of the product auctioned
+		pub product: u32,
+		/// type of auction and its prices
+		pub auctiontype: AuctionType,
+		/// block when the auction has been created
+		pub startedat: BlockNumber,
+		/// block when the auction is settled
+		pub endat: BlockNumber,
+		/// highest bidder and amount reserved
+		pub highestbid: Option<(AccountId, Balance)>,
+	}
+	pub type AuctionOf<T> =
+		Auction<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Maximum number of auctions ending in the same block
+	pub const MAX_AUCTIONS_ENDING: u32 = 64;
+	/// Maximum number of blocks a settlement can be delayed when the weight limit is reached
+	pub const MAX_SETTLEMENT_DELAY: u32 = 100;
+
+	/// Discount of a coupon on the price of the product
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Discount {
+		/// percentage of the price
+		Percentage(Percent),
+		/// fixed amount deducted from the price
+		Fixed(Balance),
+	}
+
+	/// Products where a coupon can be redeemed, they must be products of the seller
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum CouponScope {
+		/// all the products of the seller
+		AllProducts,
+		/// the products uids listed
+		Products(BoundedVec<u32, ConstU32<32>>),
+		/// the products of the (department, category)
+		Category(u32, u32),
+	}
+
+	impl CouponScope {
+		/// Number of products listed in the scope
+		pub fn products_count(&self) -> u32 {
+			match self {
+				CouponScope::Products(products) => products.len() as u32,
+				_ => 0,
+			}
+		}
+	}
+
+	/// Coupon of a seller, it can be redeemed in the validity block range (included) till the
+	/// optional limits of redemptions are reached
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Coupon<BlockNumber> {
+		/// discount on the price of the product
+		pub discount: Discount,
+		/// first block of validity
+		pub validfrom: BlockNumber,
+		/// last block of validity
+		pub validto: BlockNumber,
+		/// maximum number of redemptions from all the buyers
+		pub maxredemptions: Option<u32>,
+		/// maximum number of redemptions from the same buyer
+		pub maxredemptionsperbuyer: Option<u32>,
+		/// products where the coupon can be redeemed
+		pub scope: CouponScope,
+	}
+	pub type CouponOf<T> = Coupon<<T as frame_system::Config>::BlockNumber>;
+
+	/// Maximum number of counters of the buyers removed with a coupon, the counters over the limit
+	/// are left under the nonce of the coupon removed and never read again
+	pub const MAX_COUPON_BUYERS_REMOVED: u32 = 1_000;
+
+	/// Subscription of a buyer to a product, the amount is charged every period till the buyer
+	/// unsubscribes or the balance is insufficient
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Subscription<AccountId, BlockNumber> {
+		/// account receiving the payments
+		pub seller: AccountId,
+		/// amount charged every period, the price of the product when subscribed
+		pub amount: Balance,
+		/// billing period in blocks
+		pub period: BlockNumber,
+		/// block of the next charge
+		pub nextbilling: BlockNumber,
+	}
+	pub type SubscriptionOf<T> = Subscription<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Maximum number of subscriptions charged in the same block
+	pub const MAX_BILLINGS_PER_BLOCK: u32 = 64;
+	/// Maximum number of blocks a charge can be delayed when the billing queue of its block is full
+	pub const MAX_BILLING_DELAY: u32 = 100;
+	/// Maximum number of subscribers of a product, the subscriptions are removed with the product
+	pub const MAX_SUBSCRIBERS: u32 = 1_000;
+
+	/// Line of an invoice, the description and the price are taken from the product
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct InvoiceLine {
+		/// uid of the product invoiced
+		pub product: u32,
+		pub description: ShortText,
+		pub quantity: u32,
+		/// unit price of the product
+		pub price: Balance,
+		/// price * quantity
+		pub amount: Balance,
+	}
+
+	/// Maximum number of lines of an invoice
+	pub const MAX_INVOICE_LINES: u32 = 32;
+
+	/// Invoice issued from a seller, the record is built only from the chain data so its hash
+	/// can be verified from anybody
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Invoice<AccountId, BlockNumber> {
+		/// account issuing the invoice
+		pub seller: AccountId,
+		/// name and address of the seller
+		pub sellername: ShortText,
+		pub selleraddress: Text,
+		/// account invoiced
+		pub buyer: AccountId,
+		pub lines: BoundedVec<InvoiceLine, ConstU32<MAX_INVOICE_LINES>>,
+		/// currency of all the lines
+		pub currency: CurrencyCode,
+		/// sum of the amounts of the lines
+		pub total: Balance,
+		/// block when the invoice has been issued
+		pub issuedat: BlockNumber,
+	}
+	pub type InvoiceOf<T> =
+		Invoice<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+	/// Outcome of the verification of a certification of a seller
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum AttestationStatus {
+		/// the certification has been verified with its issuer
+		Attested,
+		/// the certification is not valid
+		Rejected,
+	}
+
+	/// Verification of a certification of a seller, it's removed when the seller changes the
+	/// certification
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Attestation<AccountId, BlockNumber> {
+		pub verifier: AccountId,
+		pub status: AttestationStatus,
+		/// block when the certification has been verified
+		pub verifiedat: BlockNumber,
+		/// last block of validity
+		pub expiry: BlockNumber,
+	}
+	pub type AttestationOf<T> = Attestation<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Maximum KYC level of a seller: 1 identity, 2 address, 3 company registration
+	pub const MAX_KYC_LEVEL: u8 = 3;
+
+	/// KYC level of a seller, it's removed when the seller changes its type, name or address
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Kyc<AccountId, BlockNumber> {
+		/// from 1 to MAX_KYC_LEVEL
+		pub level: u8,
+		pub verifier: AccountId,
+		/// block when the seller has been verified
+		pub verifiedat: BlockNumber,
+		/// last block of validity
+		pub expiry: BlockNumber,
+	}
+	pub type KycOf<T> =
+		Kyc<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
 	// we use a safe crypto hashing by blake2_128
 	// Seller data storage
 	#[pallet::storage]
 	#[pallet::getter(fn get_seller)]
 	pub(super) type Sellers<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, T::AccountId, SellerInfoOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_iso_country)]
 	pub(super) type IsoCountries<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, CountryCode, ShortText, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_products_department)]
 	pub(super) type ProductDepartments<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, u32, Text, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_products_category)]
 	pub(super) type ProductCategories<T: Config> =
-		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, u32, Text, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_currency)]
 	pub(super) type Currencies<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, CurrencyCode, CurrencyInfo>;
+
+	// Assets settling the currencies, the key is the currency code
+	#[pallet::storage]
+	#[pallet::getter(fn get_currency_asset)]
+	pub(super) type CurrencyAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, CurrencyCode, T::AssetId>;
+
+	// Tax rates, the key is (country code, optional (department, category)), the rate without
+	// category applies to all the products of the country
+	#[pallet::storage]
+	#[pallet::getter(fn get_tax_rate)]
+	pub(super) type TaxRates<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CountryCode,
+		Blake2_128Concat,
+		Option<(u32, u32)>,
+		Permill,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_products_color)]
 	pub(super) type ProductColors<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, u32, ShortText, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_products_size)]
-	pub(super) type ProductSizes<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+	pub(super) type ProductSizes<T: Config> = StorageMap<_, Blake2_128Concat, u32, SizeTable>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_product)]
-	pub(super) type Products<T: Config> = StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+	pub(super) type Products<T: Config> = StorageMap<_, Blake2_128Concat, u32, ProductInfo>;
+
+	// Seller account who created the product, the key is the product uid. The products stored
+	// before the owners have no entry till their seller is assigned from the curator origin.
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_owner)]
+	pub(super) type ProductOwner<T: Config> = StorageMap<_, Blake2_128Concat, u32, T::AccountId>;
+
+	// Status of the products, the key is the product uid. The new products start as draft, the
+	// products stored before the statuses have no entry and are active.
+	#[pallet::storage]
+	pub(super) type ProductStatuses<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, ProductStatus>;
+
+	// Index of the products by seller account and product uid
+	#[pallet::storage]
+	pub(super) type ProductsBySeller<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, u32, ()>;
+
+	// Index of the products by (department, category) and product uid
+	#[pallet::storage]
+	pub(super) type ProductsByCategory<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (u32, u32), Blake2_128Concat, u32, ()>;
+
+	// Index of the products by currency code and product uid
+	#[pallet::storage]
+	pub(super) type ProductsByCurrency<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CurrencyCode, Blake2_128Concat, u32, ()>;
+
+	// Product holding the GTIN (the upc field normalized to 14 digits), the GTIN is unique unless
+	// the products are registered from resellers
+	#[pallet::storage]
+	pub(super) type GtinOwner<T: Config> = StorageMap<_, Blake2_128Concat, Gtin, u32>;
+
+	// Index of the products of the resellers by GTIN and product uid
+	#[pallet::storage]
+	pub(super) type ProductsByGtin<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Gtin, Blake2_128Concat, u32, ()>;
+
+	// Sellers allowed to register products with the GTIN of products of other sellers
+	#[pallet::storage]
+	#[pallet::getter(fn get_reseller)]
+	pub(super) type Resellers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	// Attestations of the certifications of the sellers by seller and index of the certification
+	#[pallet::storage]
+	#[pallet::getter(fn get_certification_attestation)]
+	pub(super) type CertificationAttestations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u32,
+		AttestationOf<T>,
+	>;
+
+	// KYC level of the sellers
+	#[pallet::storage]
+	#[pallet::getter(fn get_seller_kyc)]
+	pub(super) type SellerKyc<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, KycOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_shipper)]
-	pub(super) type Shippers<T: Config> = StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+	pub(super) type Shippers<T: Config> = StorageMap<_, Blake2_128Concat, u32, ShipperInfo>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_shipper_rate)]
 	pub(super) type ShippingRates<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, u32, ShippingRateTable>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_iso_dialcode)]
 	pub(super) type IsoDialcode<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, CountryCode, ShortText, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_login_data)]
 	pub(super) type LoginData<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, Text, EncryptedData, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_email_account)]
-	pub(super) type EmailAccount<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId>;
+	pub(super) type EmailAccount<T: Config> = StorageMap<_, Blake2_128Concat, Text, T::AccountId>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_encrypted_seed)]
 	pub(super) type EmailEncryptedSeed<T: Config> =
-		StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, Text, EncryptedData, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_product_model)]
 	pub(super) type ProductModels<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, u32, ProductModelInfo>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_brand)]
-	pub(super) type Brands<T: Config> = StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+	pub(super) type Brands<T: Config> = StorageMap<_, Blake2_128Concat, u32, BrandInfo>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_manufacturer)]
 	pub(super) type Manufacturers<T: Config> =
-		StorageMap<_, Blake2_128Concat, u32, Vec<u8>, ValueQuery>;
+		StorageMap<_, Blake2_128Concat, u32, ManufacturerInfo>;
+
+	// Orders storage, the key is the order uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_order)]
+	pub(super) type Orders<T: Config> = StorageMap<_, Blake2_128Concat, u32, OrderOf<T>>;
+
+	// Return windows of the orders delivered, the key is the order uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_return_window)]
+	pub(super) type ReturnWindows<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, ReturnWindowOf<T>>;
+
+	// Purchase receipts issued from the sellers, the key is (product uid, buyer) and the value is
+	// the seller
+	#[pallet::storage]
+	#[pallet::getter(fn get_purchase_receipt)]
+	pub(super) type PurchaseReceipts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	// Reviews of the products, the key is (product uid, reviewer)
+	#[pallet::storage]
+	#[pallet::getter(fn get_review)]
+	pub(super) type Reviews<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, ReviewOf<T>>;
+
+	// Ratings aggregate of the products, the key is the product uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_rating)]
+	pub(super) type ProductRatings<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, RatingSummary, ValueQuery>;
+
+	// Ratings aggregate of the sellers
+	#[pallet::storage]
+	#[pallet::getter(fn get_seller_rating)]
+	pub(super) type SellerRatings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RatingSummary, ValueQuery>;
+
+	// Escrows storage, the key is the escrow uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_escrow)]
+	pub(super) type Escrows<T: Config> = StorageMap<_, Blake2_128Concat, u32, EscrowOf<T>>;
+
+	// Evidences submitted in the dispute of an escrow, the key is the escrow uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_dispute_evidences)]
+	pub(super) type DisputeEvidences<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, BoundedVec<EvidenceOf<T>, ConstU32<32>>, ValueQuery>;
+
+	// Quantity in stock of the products, the key is (product uid, color uid, size uid), the color
+	// and size are 0 when the product has no such variants
+	#[pallet::storage]
+	#[pallet::getter(fn get_stock)]
+	pub(super) type Stock<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, u32>,
+		),
+		u32,
+		ValueQuery,
+	>;
+
+	// Deposit reserved from the seller to store its data
+	#[pallet::storage]
+	#[pallet::getter(fn get_seller_deposit)]
+	pub(super) type SellerDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	// Deposit reserved from the seller to store the data of a product, the key is the product uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_product_deposit)]
+	pub(super) type ProductDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Balance, ValueQuery>;
+
+	// Curators allowed to maintain the reference data of a domain
+	#[pallet::storage]
+	#[pallet::getter(fn get_curator)]
+	pub(super) type Curators<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, CuratorDomain, ()>;
+
+	// Number of references from sellers, products and other reference data to the reference data
+	#[pallet::storage]
+	#[pallet::getter(fn get_reference_count)]
+	pub(super) type ReferenceCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, ReferenceKey, u32, ValueQuery>;
+
+	// Auctions of the products
+	#[pallet::storage]
+	#[pallet::getter(fn get_auction)]
+	pub(super) type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, u32, AuctionOf<T>>;
+
+	// Auctions to settle, the key is the end block
+	#[pallet::storage]
+	#[pallet::getter(fn get_auctions_ending_at)]
+	pub(super) type AuctionsEndingAt<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		BoundedVec<u32, ConstU32<MAX_AUCTIONS_ENDING>>,
+		ValueQuery,
+	>;
+
+	// Coupons of the sellers, the key is (seller, hash of the coupon code)
+	#[pallet::storage]
+	#[pallet::getter(fn get_coupon)]
+	pub(super) type Coupons<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, CouponOf<T>>;
+
+	// Nonce of the coupons of the sellers, the key is (seller, hash of the coupon code), a coupon
+	// created again with the same code gets a new nonce and its redemptions start from zero
+	#[pallet::storage]
+	#[pallet::getter(fn get_coupon_nonce)]
+	pub(super) type CouponNonces<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, u64>;
+
+	// Nonce assigned to the next coupon created
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_coupon_nonce)]
+	pub(super) type NextCouponNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	// Number of redemptions of a coupon, the key is the nonce of the coupon
+	#[pallet::storage]
+	#[pallet::getter(fn get_coupon_redemptions)]
+	pub(super) type CouponRedemptions<T: Config> =
+		StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+	// Number of redemptions of a coupon from a buyer, the key is (nonce of the coupon, buyer),
+	// stored only for the coupons limiting the redemptions per buyer
+	#[pallet::storage]
+	#[pallet::getter(fn get_coupon_buyer_redemptions)]
+	pub(super) type CouponBuyerRedemptions<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	// Billing period in blocks of the products sold as subscription, the key is the product uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_subscription_plan)]
+	pub(super) type SubscriptionPlans<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, T::BlockNumber>;
+
+	// Subscriptions of the buyers, the key is (product uid, subscriber)
+	#[pallet::storage]
+	#[pallet::getter(fn get_subscription)]
+	pub(super) type Subscriptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		SubscriptionOf<T>,
+	>;
+
+	// Number of subscriptions of the products, the key is the product uid
+	#[pallet::storage]
+	#[pallet::getter(fn get_subscriber_count)]
+	pub(super) type SubscriberCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+	// Subscriptions (product uid, subscriber) to charge, the key is the billing block
+	#[pallet::storage]
+	#[pallet::getter(fn get_billing_queue)]
+	pub(super) type BillingQueue<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		BoundedVec<(u32, T::AccountId), ConstU32<MAX_BILLINGS_PER_BLOCK>>,
+		ValueQuery,
+	>;
+
+	// Invoices storage, the key is the invoice uid that is also the id of the anchored document
+	#[pallet::storage]
+	#[pallet::getter(fn get_invoice)]
+	pub(super) type Invoices<T: Config> = StorageMap<_, Blake2_128Concat, u32, InvoiceOf<T>>;
 
 	// Events definitions
 	#[pallet::event]
@@ -133,30 +805,78 @@ pub mod pallet {
 		MarketPlaceDepartmentDestroyed(u32),     // Department has been destroyed/removed
 		MarketPlaceCategoryCreated(u32, u32, Vec<u8>), // New producct category has been created
 		MarketPlaceCategoryDestroyed(u32, u32),  // Product category has been destroyed
-		MarketPlaceSellerCreated(T::AccountId, Vec<u8>), // New seller has been created
+		MarketPlaceSellerCreated(T::AccountId, SellerInfoOf<T>), // New seller has been created
 		MarketPlaceSellerDestroyed(T::AccountId), // Seller destroyed
-		MarketPlaceProductUpdated(u32, Vec<u8>), // A product has been created or updated
+		MarketPlaceProductUpdated(u32, ProductInfo), // A product has been created or updated
+		MarketPlaceProductDestroyed(u32),        // A product has been removed
 		MarketPlaceIsoDialCodeCreated(Vec<u8>, Vec<u8>), // New country dial code has been created
 		MarketPlaceIsoDialCodeDestroyed(Vec<u8>), // A country dial code has been destroyed
-		MarketPlaceCurrencyCodeCreated(Vec<u8>, Vec<u8>), // A new currency has been created
+		MarketPlaceCurrencyCodeCreated(Vec<u8>, CurrencyInfo), // A new currency has been created
 		MarketPlaceCurrencyDestroyed(Vec<u8>),   // A currency has been destroyed
 		MarketPlaceColorCreated(u32, Vec<u8>),   // A new color has been created
 		MarketPlaceColorDestroyed(u32),          // A color has been removed
-		MarketPlaceSizeCreated(u32, Vec<u8>),    // A new size table has been created
+		MarketPlaceSizeCreated(u32, SizeTable),  // A new size table has been created
 		MarketPlaceSizeDestroyed(u32),           // A size table has been removed
 		MarketPlaceLoginDataCreated(Vec<u8>, Vec<u8>, T::AccountId), // A new login data has been created
 		MarketPlaceLoginDataDestroyed(Vec<u8>),  // A login data has been destroyed
 		MarketPlaceLoginPwdChanged(Vec<u8>, Vec<u8>), // password changed
-		MarketPlaceManufacturerCreated(u32, Vec<u8>), // A new manufacturer has been created
+		MarketPlaceManufacturerCreated(u32, ManufacturerInfo), // A new manufacturer has been created
 		MarketPlaceManufacturerDestroyed(u32),   // A manufacturer has been removed
-		MarketPlaceShipperCreated(u32, Vec<u8>), // A new shipper has been created
+		MarketPlaceShipperCreated(u32, ShipperInfo), // A new shipper has been created
 		MarketPlaceShipperDestroyed(u32),        // A shipper has been removed
-		MarketShippingRateCreated(u32, Vec<u8>), // A new shipping rate has been created
+		MarketShippingRateCreated(u32, ShippingRateTable), // A new shipping rate has been created
 		MarketShippingRateDestroyed(u32),        // A shipping rate has been removed
-		MarketPlaceBrandCreated(u32, Vec<u8>),   // A new brand has been created
+		MarketPlaceBrandCreated(u32, BrandInfo), // A new brand has been created
 		MarketPlaceBrandDestroyed(u32),          // A brand has been removed
-		MarketPlaceProductModelCreated(u32, Vec<u8>), // a new product model has been created
+		MarketPlaceProductModelCreated(u32, ProductModelInfo), // a new product model has been created
 		MarketPlaceProductModelDestroyed(u32),   // a product model has been removed
+		MarketPlaceOrderPlaced(u32, T::AccountId, T::AccountId, Balance), // A new order has been placed
+		MarketPlaceOrderShipped(u32),            // The order has been shipped
+		MarketPlaceOrderDelivered(u32, Balance), // Delivery confirmed, payment released
+		MarketPlaceOrderCancelled(u32),          // The order has been cancelled
+		MarketPlaceReceiptIssued(u32, T::AccountId), // A purchase receipt has been issued
+		MarketPlaceProductReviewed(u32, T::AccountId, u8), // A product has been reviewed
+		MarketPlaceProductRatingUpdated(u32, u32, u32), // New ratings count and sum of a product
+		MarketPlaceSellerRatingUpdated(T::AccountId, u32, u32), // New ratings count and sum of a seller
+		MarketPlaceStockUpdated(u32, u32, u32, u32), // The stock of a product variant has been set
+		MarketPlaceSaleRecorded(u32, u32, u32, u32), // A sale has been deducted from the stock
+		MarketPlaceEscrowOpened(u32, T::AccountId, T::AccountId, Balance), // A new escrow has been opened
+		MarketPlaceEscrowReleased(u32, Balance), // The escrow has been released to the seller
+		MarketPlaceDisputeOpened(u32, T::AccountId), // A dispute has been opened on the escrow
+		MarketPlaceEvidenceSubmitted(u32, T::AccountId, Vec<u8>), // A new evidence of the dispute
+		MarketPlaceDisputeResolved(u32, Balance, Balance), // Dispute resolved (buyer, seller amounts)
+		MarketPlaceCuratorAdded(T::AccountId, CuratorDomain), // A curator of the domain has been added
+		MarketPlaceCuratorRemoved(T::AccountId, CuratorDomain), // A curator has been removed
+		MarketPlaceAuctionCreated(u32, T::AccountId, u32, T::BlockNumber), // New auction
+		MarketPlaceBidPlaced(u32, T::AccountId, Balance), // A bid has been placed
+		MarketPlaceAuctionSettled(u32, T::AccountId, T::AccountId, u32, Balance), // Auction won
+		MarketPlaceAuctionClosed(u32),           // The auction ended without a winner
+		MarketPlaceCouponCreated(T::AccountId, T::Hash), // New coupon of the seller
+		MarketPlaceCouponDestroyed(T::AccountId, T::Hash), // A coupon has been removed
+		MarketPlaceCouponRedeemed(T::Hash, T::AccountId, T::AccountId, u32, Balance), // Coupon used
+		MarketPlaceSubscriptionPlanSet(u32, Option<T::BlockNumber>), // Billing period of a product
+		MarketPlaceSubscribed(u32, T::AccountId, Balance), // New subscription to a product
+		MarketPlaceSubscriptionCharged(u32, T::AccountId, Balance), // A period has been charged
+		MarketPlaceSubscriptionLapsed(u32, T::AccountId), // The subscription could not be charged
+		MarketPlaceUnsubscribed(u32, T::AccountId), // The subscriber has unsubscribed
+		MarketPlaceCurrencyAssetSet(Vec<u8>, Option<T::AssetId>), // Asset of a currency changed
+		MarketPlaceProductBought(u32, T::AccountId, T::AccountId, u32, Balance), // Product paid
+		MarketPlaceTaxRateSet(Vec<u8>, Option<(u32, u32)>, Option<Permill>), // Tax rate changed
+		MarketPlaceInvoiceIssued(u32, T::AccountId, T::AccountId, T::Hash), // Invoice anchored
+		MarketPlaceResellerSet(T::AccountId, bool), // The account has been set or unset as reseller
+		MarketPlaceAttestationSet(T::AccountId, u32, AttestationStatus, T::BlockNumber), // Verified
+		MarketPlaceSellerKycSet(T::AccountId, u8, T::BlockNumber), // KYC level of a seller set
+		MarketPlaceReturnRequested(u32, T::BlockNumber), // Return requested, respond till block
+		MarketPlaceReturnApproved(u32, Balance), // The payment has been refunded to the buyer
+		MarketPlaceReturnRejected(u32),          // The seller rejected the return
+		MarketPlacePaymentReleased(u32, Balance), // The payment is available to the seller
+		MarketPlaceProductPublished(u32),        // The product is listed in the catalogue
+		MarketPlaceProductPaused(u32),           // The product is temporarily not listed
+		MarketPlaceProductDiscontinued(u32),     // The product is permanently not listed
+		MarketPlaceProductRefused(u32, DispatchError), // A product of a batch has not been stored
+		MarketPlaceProductsBatchStored(T::AccountId, u32, u32), // Batch stored (total, stored)
+		MarketPlaceProductOwnerAssigned(u32, T::AccountId), // Seller of a product stored before
+		MarketPlaceSubscriptionNotRescheduled(u32, T::AccountId), // Billing queues full, not charged
 	}
 
 	// Errors inform users that something went wrong.
@@ -249,14 +969,14 @@ pub mod pallet {
 		/// the inout fiels is not set for the the country, it should be 0 for included, 1 for
 		/// excluded country with default worldwide
 		IncludedExcludedCountryValueIsMissing,
-		/// The latitude of the center point for the shipment area, is missing
-		ShipmentAreaCenterLatitudeIsMissing,
-		/// The longitude of the center point for the shipment area, is missing
-		ShipmentAreaCenterLongitudeIsMissing,
-		/// The latitude of the border point for the shipment area, is missing
-		ShipmentAreaBorderLatitudeIsMissing,
-		/// The longitude of the border point for the shipment area, is missing
-		ShipmentAreaBorderLongituteIsMissing,
+		/// The latitude of the center point for the shipment area is out of range
+		ShipmentAreaCenterLatitudeIsWrong,
+		/// The longitude of the center point for the shipment area is out of range
+		ShipmentAreaCenterLongitudeIsWrong,
+		/// The latitude of the border point for the shipment area is out of range
+		ShipmentAreaBorderLatitudeIsWrong,
+		/// The longitude of the border point for the shipment area is out of range
+		ShipmentAreaBorderLongitudeIsWrong,
 		/// Seller Social Url is wrong
 		SellerSocialUrlIsWrong,
 		/// Seller web site is wrong
@@ -410,14 +1130,14 @@ pub mod pallet {
 		DimensionWrongWeight,
 		/// UPC code is missing or is wrong
 		UniversalProductCodeIsWrong,
-		/// Center Latitude of the area is missing
-		CenterLatitudeIsMissing,
-		/// Center Longitude of the area is missing
-		CenterLongitudeIsMissing,
-		/// Border Latitude of the area is missing
-		BorderLatitudeIsMissing,
-		/// Center Longitude of the area is missing
-		BorderLongitudeIsMissing,
+		/// Center Latitude of the area is out of range
+		CenterLatitudeIsWrong,
+		/// Center Longitude of the area is out of range
+		CenterLongitudeIsWrong,
+		/// Border Latitude of the area is out of range
+		BorderLatitudeIsWrong,
+		/// Border Longitude of the area is out of range
+		BorderLongitudeIsWrong,
 		/// Invalid Api Url, should be an https or http address
 		InvalidApiUrl,
 		/// Language code is wrong
@@ -432,6 +1152,192 @@ pub mod pallet {
 		EmailHashNotFound,
 		/// Signer of transaction is not authorized to execute it
 		SignerIsNotAuthorized,
+		/// Product has not been found on chain
+		ProductNotFound,
+		/// The seller of the product has not been found on chain
+		ProductOwnerNotFound,
+		/// Order uid cannot be zero
+		OrderUidCannotBeZero,
+		/// Order uid is already present on chain
+		OrderAlreadyPresent,
+		/// Order has not been found on chain
+		OrderNotFound,
+		/// Quantity ordered cannot be zero
+		OrderQuantityCannotBeZero,
+		/// The total amount of the order is too large
+		OrderAmountOverflow,
+		/// Buyer and seller cannot be the same account
+		BuyerCannotBeSeller,
+		/// The current status of the order does not allow the operation
+		OrderStatusIsWrong,
+		/// The payment reserved could not be fully transferred to the seller
+		PaymentTransferFailed,
+		/// The purchase receipt of the product has not been found
+		PurchaseReceiptNotFound,
+		/// The rating must be between 1 and 5
+		RatingIsWrong,
+		/// The review hash is too long
+		ReviewHashIsTooLong,
+		/// The color or the size is not a variant of the product
+		VariantNotFound,
+		/// The quantity in stock is not enough
+		OutOfStock,
+		/// The quantity cannot be zero
+		QuantityCannotBeZero,
+		/// Escrow uid cannot be zero
+		EscrowUidCannotBeZero,
+		/// The escrow is already present
+		EscrowAlreadyPresent,
+		/// The escrow has not been found
+		EscrowNotFound,
+		/// The current status of the escrow does not allow the operation
+		EscrowStatusIsWrong,
+		/// The hash of the evidence is wrong
+		EvidenceHashIsWrong,
+		/// The ipfs address of the evidence is wrong
+		EvidenceIpfsAddressIsWrong,
+		/// Too many evidences have been submitted for the dispute
+		TooManyEvidences,
+		/// Arrays and objects of the json are nested too deeply
+		JsonIsTooDeep,
+		/// The country name is too long, maximum 64 bytes
+		CountryNameTooLong,
+		/// International Dial code is too long, maximum 64 bytes
+		DialcodeTooLong,
+		/// The encrypted seed is too long, maximum 1024 bytes
+		EncryptedSeedTooLong,
+		/// The signer is not a curator of the reference data
+		SignerIsNotCurator,
+		/// The curator is already present for the domain
+		CuratorAlreadyPresent,
+		/// The curator has not been found for the domain
+		CuratorNotFound,
+		/// The reference data is in use from sellers, products or other reference data
+		InUse,
+		/// Auction uid cannot be zero
+		AuctionUidCannotBeZero,
+		/// The auction is already present on chain
+		AuctionAlreadyPresent,
+		/// The auction has not been found on chain
+		AuctionNotFound,
+		/// The duration of the auction cannot be zero
+		AuctionDurationCannotBeZero,
+		/// The prices of the dutch auction are wrong, the start price must be higher than the
+		/// floor price and the decrement cannot be zero
+		DutchAuctionPricesAreWrong,
+		/// Too many auctions are ending in the same block
+		TooManyAuctionsEnding,
+		/// The auction has ended
+		AuctionHasEnded,
+		/// The bid is lower than the reserve price, the highest bid or the current price
+		BidTooLow,
+		/// The coupon is already present on chain
+		CouponAlreadyPresent,
+		/// The coupon has not been found on chain
+		CouponNotFound,
+		/// The discount of the coupon cannot be zero
+		CouponDiscountCannotBeZero,
+		/// The validity of the coupon is wrong, the last block must follow the first block and
+		/// the current block
+		CouponValidityIsWrong,
+		/// The products of the coupon cannot be empty
+		CouponProductsCannotBeEmpty,
+		/// The coupon is not valid in the current block
+		CouponIsNotValid,
+		/// The coupon cannot be redeemed for the product
+		CouponNotValidForProduct,
+		/// The maximum number of redemptions of the coupon has been reached
+		CouponRedemptionsExceeded,
+		/// The billing period of the subscription cannot be zero
+		SubscriptionPeriodCannotBeZero,
+		/// The product is not sold as subscription
+		ProductIsNotSubscription,
+		/// The buyer is already subscribed to the product
+		AlreadySubscribed,
+		/// The subscription has not been found on chain
+		SubscriptionNotFound,
+		/// The billing queue is full for the next blocks
+		TooManyBillings,
+		/// The currency of the product is not mapped to an asset
+		CurrencyAssetNotFound,
+		/// The tax rate is not present on chain
+		TaxRateNotFound,
+		/// Invoice uid cannot be zero
+		InvoiceUidCannotBeZero,
+		/// The invoice is already present on chain
+		InvoiceAlreadyPresent,
+		/// The invoice must have at least one line
+		InvoiceLinesCannotBeEmpty,
+		/// The invoice has too many lines
+		TooManyInvoiceLines,
+		/// The products of the invoice must have the same currency
+		InvoiceCurrencyMismatch,
+		/// The GTIN is already registered from a product of another seller
+		GtinAlreadyRegistered,
+		/// The signer is not a verifier of the sellers
+		SignerIsNotVerifier,
+		/// The certification is not present in the seller data
+		CertificationNotFound,
+		/// The expiry must be a future block
+		ExpiryIsWrong,
+		/// The KYC level is over the maximum
+		KycLevelIsWrong,
+		/// The order has no return window
+		ReturnWindowNotFound,
+		/// The return window of the order is closed
+		ReturnWindowClosed,
+		/// The return window of the order is still open
+		ReturnWindowIsOpen,
+		/// The current status of the return does not allow the operation
+		ReturnStatusIsWrong,
+		/// The payment reserved could not be fully refunded to the buyer
+		RefundFailed,
+		/// The current status of the product does not allow the operation
+		ProductStatusIsWrong,
+		/// The product is not active
+		ProductIsNotActive,
+		/// The batch of products cannot be empty
+		ProductsBatchCannotBeEmpty,
+		/// The product has reached the maximum number of subscribers
+		TooManySubscribers,
+		/// The product has already an owner
+		ProductOwnerAlreadySet,
+		/// The discounted price of the product cannot be zero
+		DiscountedPriceCannotBeZero,
+		/// The deadline for the seller to respond to the return has passed
+		ReturnResponseExpired,
+		/// The seller can still respond to the return till the deadline
+		ReturnResponseIsPending,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		// settle the auctions ending in the block
+		// and charge the subscriptions billed in the block
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let limit = T::MaxInitializeWeight::get();
+			// settle the auctions ending in the block up to the weight limit, the other ones are
+			// delayed to the following blocks
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for uid in AuctionsEndingAt::<T>::take(n) {
+				if weight.saturating_add(Self::settlement_weight()).all_lte(limit) {
+					weight.saturating_accrue(Self::settle_auction(uid));
+				} else {
+					weight.saturating_accrue(Self::defer_settlement(uid, n));
+				}
+			}
+			// charge the subscriptions billed in the block with the weight left, the other charges
+			// are delayed to the following blocks
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			for (product, subscriber) in BillingQueue::<T>::take(n) {
+				if weight.saturating_add(Self::charge_weight()).all_lte(limit) {
+					weight.saturating_accrue(Self::charge_subscription(product, &subscriber, n));
+				} else {
+					weight.saturating_accrue(Self::defer_charge(product, &subscriber, n));
+				}
+			}
+			weight
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -447,20 +1353,21 @@ pub mod pallet {
 			countrycode: Vec<u8>,
 			countryname: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the countries
+			Self::ensure_curator(origin, CuratorDomain::Countries)?;
 			// check country code length == 2
 			ensure!(countrycode.len() == 2, Error::<T>::WrongLengthCountryCode);
+			let code: CountryCode =
+				countrycode.clone().try_into().map_err(|_| Error::<T>::WrongLengthCountryCode)?;
 			// check country name length  >= 3
 			ensure!(countryname.len() >= 3, Error::<T>::CountryNameTooShort);
+			let name: ShortText =
+				countryname.clone().try_into().map_err(|_| Error::<T>::CountryNameTooLong)?;
 			// check the country is not alreay present on chain
-			ensure!(
-				!IsoCountries::<T>::contains_key(&countrycode),
-				Error::<T>::CountryCodeAlreadyPresent
-			);
+			ensure!(!IsoCountries::<T>::contains_key(&code), Error::<T>::CountryCodeAlreadyPresent);
 			//let project = Projects::<T>::get(project_id).ok_or(Error::<T>::ProjectNotFound)?;
 			// store the Iso Country Code and Name
-			IsoCountries::<T>::insert(countrycode.clone(), countryname.clone());
+			IsoCountries::<T>::insert(code, name);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceIsoCountryCreated(countrycode, countryname));
 			// Return a successful DispatchResult
@@ -470,14 +1377,17 @@ pub mod pallet {
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_iso_country(origin: OriginFor<T>, countrycode: Vec<u8>) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the countries
+			Self::ensure_curator(origin, CuratorDomain::Countries)?;
 			// verify the country code exists
-			ensure!(IsoCountries::<T>::contains_key(&countrycode), Error::<T>::CountryCodeNotFound);
+			let code: CountryCode =
+				countrycode.clone().try_into().map_err(|_| Error::<T>::CountryCodeNotFound)?;
+			ensure!(IsoCountries::<T>::contains_key(&code), Error::<T>::CountryCodeNotFound);
+			// check the country is not in use
+			Self::ensure_not_in_use(ReferenceKey::Country(code.clone()))?;
 			// Remove country code
-			IsoCountries::<T>::take(countrycode.clone());
+			IsoCountries::<T>::take(code);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceIsoCountryDestroyed(countrycode));
 			// Return a successful DispatchResult
 			Ok(())
@@ -490,20 +1400,24 @@ pub mod pallet {
 			uid: u32,
 			description: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::DepartmentUidCannotBeZero);
 			//check description length
 			ensure!(description.len() > 3, Error::<T>::DepartmentDescriptionTooShort);
 			ensure!(description.len() < 128, Error::<T>::DepartmentDescriptionTooLong);
+			let text: Text = description
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::DepartmentDescriptionTooLong)?;
 			// check the department is not alreay present on chain
 			ensure!(
 				!ProductDepartments::<T>::contains_key(uid),
 				Error::<T>::DepartmentAlreadyPresent
 			);
 			// store the department
-			ProductDepartments::<T>::insert(uid, description.clone());
+			ProductDepartments::<T>::insert(uid, text);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceDepartmentCreated(uid, description));
 			// Return a successful DispatchResult
@@ -513,14 +1427,15 @@ pub mod pallet {
 		#[pallet::call_index(5)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_product_department(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// verify the department exists
 			ensure!(ProductDepartments::<T>::contains_key(uid), Error::<T>::DepartmentNotFound);
+			// check the department has no categories
+			ensure!(ProductCategories::<T>::iter_prefix(uid).next().is_none(), Error::<T>::InUse);
 			// Remove department
 			ProductDepartments::<T>::take(uid);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceDepartmentDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
@@ -534,8 +1449,8 @@ pub mod pallet {
 			uidcategory: u32,
 			description: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// check uid department >0
 			ensure!(uiddepartment > 0, Error::<T>::DepartmentUidCannotBeZero);
 			// check uid category >0
@@ -543,6 +1458,10 @@ pub mod pallet {
 			//check description length
 			ensure!(description.len() > 3, Error::<T>::CategoryDescriptionTooShort);
 			ensure!(description.len() < 128, Error::<T>::CategoryDescriptionTooLong);
+			let text: Text = description
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::CategoryDescriptionTooLong)?;
 			// check the department is  alreay present on chain
 			ensure!(
 				ProductDepartments::<T>::contains_key(uiddepartment),
@@ -554,7 +1473,7 @@ pub mod pallet {
 				Error::<T>::ProductCategoryAlreadyPresent
 			);
 			// store the department
-			ProductCategories::<T>::insert(uiddepartment, uidcategory, description.clone());
+			ProductCategories::<T>::insert(uiddepartment, uidcategory, text);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceCategoryCreated(
 				uiddepartment,
@@ -572,532 +1491,325 @@ pub mod pallet {
 			uiddepartment: u32,
 			uidcategory: u32,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// verify the department/category exists
 			ensure!(
 				ProductCategories::<T>::contains_key(uiddepartment, uidcategory),
 				Error::<T>::ProductCategoryNotFound
 			);
+			// check the category is not in use
+			Self::ensure_not_in_use(ReferenceKey::Category(uiddepartment, uidcategory))?;
 			// Remove department
 			ProductCategories::<T>::take(uiddepartment, uidcategory);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceCategoryDestroyed(uiddepartment, uidcategory));
 			// Return a successful DispatchResult
 			Ok(())
 		}
-		/// Create a new seller
+		/// Create a new seller or update the seller data.
+		/// The optional seller account is set when the signer acts as a proxy for gasless
+		/// transactions, an existing seller can be updated only from the proxy account stored in
+		/// its data.
 		#[pallet::call_index(8)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
+		#[pallet::weight(T::WeightInfo::create_update_seller(
+			info.references().len() as u32,
+			MAX_SELLER_REFERENCES
+		))]
 		pub fn create_update_seller(
 			origin: OriginFor<T>,
-			configuration: Vec<u8>,
-		) -> DispatchResult {
+			selleraccount: Option<T::AccountId>,
+			info: SellerInfoOf<T>,
+		) -> DispatchResultWithPostInfo {
 			// check the request is signed
 			let mut sender = ensure_signed(origin)?;
 			let originalsigner = sender.clone();
-			//check configuration length
-			ensure!(configuration.len() > 12, Error::<T>::ConfigurationTooShort);
-			ensure!(configuration.len() < 8192, Error::<T>::ConfigurationTooLong);
-			// check json validity
-			ensure!(json_check_validity(configuration.clone()), Error::<T>::InvalidJson);
-			// checking seller type 1= Company, 2= Freelancer, 3= Individual, 4 == Government Agency
-			// 4 == NGO
-			let sellertype =
-				json_get_value(configuration.clone(), "sellertype".as_bytes().to_vec());
-			let sellertypeu32 = vecu8_to_u32(sellertype);
-			ensure!(
-				sellertypeu32 == 1 ||
-					sellertypeu32 == 2 || sellertypeu32 == 3 ||
-					sellertypeu32 == 4 || sellertypeu32 == 5,
-				Error::<T>::SellerTypeInvalid
-			);
 			// checking company name or name/surname
-			let sellername = json_get_value(configuration.clone(), "name".as_bytes().to_vec());
-			ensure!(sellername.len() > 5, Error::<T>::SellerNameTooShort);
+			ensure!(info.name.len() > 5, Error::<T>::SellerNameTooShort);
 			// address we check for maximum lenght of 128 bytes
-			let selleraddress =
-				json_get_value(configuration.clone(), "address".as_bytes().to_vec());
-			ensure!(selleraddress.len() < 128, Error::<T>::SellerAddressTooLong);
+			ensure!(info.address.len() < 128, Error::<T>::SellerAddressTooLong);
 			// zip code we check for maximum lenght of 12 bytes
-			let sellerzip = json_get_value(configuration.clone(), "zip".as_bytes().to_vec());
-			ensure!(sellerzip.len() < 13, Error::<T>::SellerZipCodeTooLong);
-			// checking the city minimum 3 bytes
-			let sellerpobox = json_get_value(configuration.clone(), "pobox".as_bytes().to_vec());
-			ensure!(sellerpobox.len() < 64, Error::<T>::SellerPoBoxTooLong);
-			// checking the city minimum 3 bytes
-			let sellercity = json_get_value(configuration.clone(), "city".as_bytes().to_vec());
-			ensure!(sellercity.len() > 5, Error::<T>::SellerCityTooShort);
-			ensure!(sellercity.len() < 64, Error::<T>::SellerCityTooLong);
+			ensure!(info.zip.len() < 13, Error::<T>::SellerZipCodeTooLong);
+			// checking the po box maximum 63 bytes
+			ensure!(info.pobox.len() < 64, Error::<T>::SellerPoBoxTooLong);
+			// checking the city minimum 6 bytes
+			ensure!(info.city.len() > 5, Error::<T>::SellerCityTooShort);
+			ensure!(info.city.len() < 64, Error::<T>::SellerCityTooLong);
 			// checking websites
-			let websites =
-				json_get_complexarray(configuration.clone(), "websites".as_bytes().to_vec());
-			if !websites.is_empty() {
-				let mut x = 0;
-				loop {
-					let w = json_get_recordvalue(websites.clone(), x);
-					if w.is_empty() {
-						break
-					}
-					let weburl = json_get_value(w.clone(), "weburl".as_bytes().to_vec());
-					ensure!(aisland_validate_weburl(weburl), Error::<T>::SellerWebsiteUrlIsWrong);
-					x += 1;
-				}
+			for weburl in info.websites.iter() {
+				ensure!(
+					aisland_validate_weburl(weburl.to_vec()),
+					Error::<T>::SellerWebsiteUrlIsWrong
+				);
 			}
 			// checking social url
-			let socialurls =
-				json_get_complexarray(configuration.clone(), "socialurls".as_bytes().to_vec());
-			if !socialurls.is_empty() {
-				let mut x = 0;
-				loop {
-					let w = json_get_recordvalue(socialurls.clone(), x);
-					if w.is_empty() {
-						break
-					}
-					let socialurl = json_get_value(w.clone(), "socialurl".as_bytes().to_vec());
-					ensure!(aisland_validate_weburl(socialurl), Error::<T>::SellerSocialUrlIsWrong);
-					x += 1;
-				}
+			for socialurl in info.socialurls.iter() {
+				ensure!(
+					aisland_validate_weburl(socialurl.to_vec()),
+					Error::<T>::SellerSocialUrlIsWrong
+				);
 			}
 			// checking certifications
-			let certifications =
-				json_get_complexarray(configuration.clone(), "certifications".as_bytes().to_vec());
-			if !certifications.is_empty() {
-				let mut x = 0;
-				loop {
-					let w = json_get_recordvalue(certifications.clone(), x);
-					if w.is_empty() {
-						break
-					}
-					let certificationdescription =
-						json_get_value(configuration.clone(), "description".as_bytes().to_vec());
-					let certificateverificationurl = json_get_value(
-						configuration.clone(),
-						"verificationurl".as_bytes().to_vec(),
-					);
-					ensure!(
-						certificationdescription.len() > 3,
-						Error::<T>::SellerCertificationDescriptionTooShort
-					);
-					ensure!(
-						certificationdescription.len() <= 64,
-						Error::<T>::SellerCertificationDescriptionTooLong
-					);
-					ensure!(
-						certificateverificationurl.len() > 3,
-						Error::<T>::SellerCertificateVerificationTooShort
-					);
-					ensure!(
-						certificateverificationurl.len() <= 64,
-						Error::<T>::SellerCertificateVerificationTooLong
-					);
-					ensure!(
-						aisland_validate_weburl(certificateverificationurl.clone()),
-						Error::<T>::SellerCertificationUrlIsWrong
-					);
-					x += 1;
-				}
+			for c in info.certifications.iter() {
+				ensure!(
+					c.description.len() > 3,
+					Error::<T>::SellerCertificationDescriptionTooShort
+				);
+				ensure!(
+					c.verificationurl.len() > 3,
+					Error::<T>::SellerCertificateVerificationTooShort
+				);
+				ensure!(
+					aisland_validate_weburl(c.verificationurl.to_vec()),
+					Error::<T>::SellerCertificationUrlIsWrong
+				);
 			}
 			// checking emailinfo
-			let emailinfo = json_get_value(configuration.clone(), "emailinfo".as_bytes().to_vec());
-			ensure!(emailinfo.len() > 5, Error::<T>::SellerInfoEmailIsWrong);
-			ensure!(aisland_validate_email(emailinfo.clone()), Error::<T>::SellerInfoEmailIsWrong);
+			ensure!(info.emailinfo.len() > 5, Error::<T>::SellerInfoEmailIsWrong);
+			ensure!(
+				aisland_validate_email(info.emailinfo.to_vec()),
+				Error::<T>::SellerInfoEmailIsWrong
+			);
 			// checking email support
-			let emailsupport =
-				json_get_value(configuration.clone(), "emailsupport".as_bytes().to_vec());
-			ensure!(emailsupport.len() > 5, Error::<T>::SellerSupportEmailIsWrong);
+			ensure!(info.emailsupport.len() > 5, Error::<T>::SellerSupportEmailIsWrong);
 			ensure!(
-				aisland_validate_email(emailsupport.clone()),
+				aisland_validate_email(info.emailsupport.to_vec()),
 				Error::<T>::SellerSupportEmailIsWrong
 			);
-
 			// checking phone numbers
-			let phones = json_get_complexarray(configuration.clone(), "phones".as_bytes().to_vec());
-			if !phones.is_empty() {
-				let mut x = 0;
-				loop {
-					let w = json_get_recordvalue(phones.clone(), x);
-					if w.is_empty() {
-						break
-					}
-					let phonedescription = json_get_value(
-						configuration.clone(),
-						"phonedescription".as_bytes().to_vec(),
-					);
-					let phonenumber =
-						json_get_value(configuration.clone(), "phonebumber".as_bytes().to_vec());
-					ensure!(phonedescription.len() > 3, Error::<T>::SellerPhoneDescriptionTooShort);
-					ensure!(
-						phonedescription.len() <= 64,
-						Error::<T>::SellerPhoneDescriptionTooLong
-					);
-					ensure!(phonenumber.len() > 3, Error::<T>::SellerPhoneNumberTooShort);
-					ensure!(phonenumber.len() <= 23, Error::<T>::SellerPhoneNumberTooLong);
-					ensure!(
-						aisland_validate_phonenumber(phonenumber),
-						Error::<T>::SellerPhoneNumberIsWrong
-					);
-					x += 1;
-				}
+			for phone in info.phones.iter() {
+				ensure!(phone.description.len() > 3, Error::<T>::SellerPhoneDescriptionTooShort);
+				ensure!(phone.number.len() > 3, Error::<T>::SellerPhoneNumberTooShort);
+				ensure!(phone.number.len() <= 23, Error::<T>::SellerPhoneNumberTooLong);
+				ensure!(
+					aisland_validate_phonenumber(phone.number.to_vec()),
+					Error::<T>::SellerPhoneNumberIsWrong
+				);
 			}
-			// checking categories of products/services with the department
-			let categories =
-				json_get_complexarray(configuration.clone(), "categories".as_bytes().to_vec());
-			ensure!(!categories.is_empty(), Error::<T>::SellerCategoriesMissing);
-			let mut x = 0;
-			let mut nc = 0;
-			loop {
-				let c = json_get_recordvalue(categories.clone(), x);
-				if c.is_empty() {
-					break
-				}
-				let category =
-					json_get_value(configuration.clone(), "category".as_bytes().to_vec());
-				let department =
-					json_get_value(configuration.clone(), "department".as_bytes().to_vec());
-				let categoryu32 = vecu8_to_u32(category);
-				let departmentu32 = vecu8_to_u32(department);
+			// checking categories of products/services with the department, at least one is
+			// required
+			ensure!(!info.categories.is_empty(), Error::<T>::SellerCategoriesMissing);
+			for (department, category) in info.categories.iter() {
 				ensure!(
-					ProductCategories::<T>::contains_key(categoryu32, departmentu32),
+					ProductCategories::<T>::contains_key(department, category),
 					Error::<T>::ProductCategoryNotFound
 				);
-				x += 1;
-				nc += 1;
 			}
-			// check that we have at least one valid product category
-			ensure!(nc > 0, Error::<T>::SellerCategoriesMissing);
 			// checking included countries of shipment, if not set means worldwide less the excluded
-			// countries
-			let countries =
-				json_get_complexarray(configuration.clone(), "countries".as_bytes().to_vec());
-			ensure!(!countries.is_empty(), Error::<T>::SellercountriesMissing);
-			let mut x = 0;
-			loop {
-				let c = json_get_recordvalue(countries.clone(), x);
-				if c.is_empty() {
-					break
-				}
-				let country = json_get_value(configuration.clone(), "country".as_bytes().to_vec());
-				let inout = json_get_value(configuration.clone(), "inout".as_bytes().to_vec());
-				let inoutv = vecu8_to_u32(inout);
-				ensure!(IsoCountries::<T>::contains_key(country), Error::<T>::CountryCodeNotFound);
+			// countries, at least one is required
+			ensure!(!info.countries.is_empty(), Error::<T>::SellercountriesMissing);
+			for c in info.countries.iter() {
 				ensure!(
-					inoutv == 0 || inoutv == 1,
-					Error::<T>::IncludedExcludedCountryValueIsMissing
+					IsoCountries::<T>::contains_key(&c.country),
+					Error::<T>::CountryCodeNotFound
 				);
-				x += 1;
 			}
-			// check that we have at least one valid country
-			ensure!(x > 0, Error::<T>::SellerCategoriesMissing);
 			// delivery area can be delimited by GPS coordinates where a first point is the center
 			// of a circle and second point is the border of the same circle this is useful if a
 			// service/product can be delivered only around a certain place
-			let shipmentarea =
-				json_get_complexarray(configuration.clone(), "shipmentarea".as_bytes().to_vec());
-			if !shipmentarea.is_empty() {
-				let centerlatitude =
-					json_get_value(shipmentarea.clone(), "centerlatitude".as_bytes().to_vec());
-				let centerlongitude =
-					json_get_value(shipmentarea.clone(), "centerlongitude".as_bytes().to_vec());
-				let borderlatitude =
-					json_get_value(shipmentarea.clone(), "borderlatitude".as_bytes().to_vec());
-				let borderlongitude =
-					json_get_value(shipmentarea.clone(), "borderlongitude".as_bytes().to_vec());
+			for area in info.shipmentarea.iter() {
 				ensure!(
-					!centerlatitude.is_empty(),
-					Error::<T>::ShipmentAreaCenterLatitudeIsMissing
+					geo::valid_latitude(area.centerlatitude),
+					Error::<T>::ShipmentAreaCenterLatitudeIsWrong
 				);
 				ensure!(
-					!centerlongitude.is_empty(),
-					Error::<T>::ShipmentAreaCenterLongitudeIsMissing
+					geo::valid_longitude(area.centerlongitude),
+					Error::<T>::ShipmentAreaCenterLongitudeIsWrong
 				);
 				ensure!(
-					!borderlatitude.is_empty(),
-					Error::<T>::ShipmentAreaBorderLatitudeIsMissing
+					geo::valid_latitude(area.borderlatitude),
+					Error::<T>::ShipmentAreaBorderLatitudeIsWrong
 				);
 				ensure!(
-					!borderlongitude.is_empty(),
-					Error::<T>::ShipmentAreaBorderLongituteIsMissing
+					geo::valid_longitude(area.borderlongitude),
+					Error::<T>::ShipmentAreaBorderLongitudeIsWrong
 				);
 			}
 			// check for optional default language
-			let defaultlanguage =
-				json_get_value(configuration.clone(), "defaultlanguage".as_bytes().to_vec());
-			if !defaultlanguage.is_empty() {
+			if let Some(defaultlanguage) = &info.defaultlanguage {
 				ensure!(
-					aisland_validate_languagecode(defaultlanguage),
+					aisland_validate_languagecode(defaultlanguage.to_vec()),
 					Error::<T>::SellerDefaultLanguageIsWrong
 				);
 			}
 			// check for optional default unit of measurement
-			let defaultunitmeasurement =
-				json_get_value(configuration.clone(), "defaultunitmeasurement".as_bytes().to_vec());
-			if !defaultunitmeasurement.is_empty() {
+			if let Some(defaultunitmeasurement) = &info.defaultunitmeasurement {
 				ensure!(
-					aisland_validate_unitmeasurement(defaultunitmeasurement),
+					aisland_validate_unitmeasurement(defaultunitmeasurement.to_vec()),
 					Error::<T>::SellerDefaultUnitMeasurementIsWrong
 				);
 			}
 			// check for default return policy in days
-			let defaultreturnpolicy =
-				json_get_value(configuration.clone(), "defaultreturnpolicy".as_bytes().to_vec());
-			if !defaultreturnpolicy.is_empty() {
-				let drp = vecu8_to_u32(defaultreturnpolicy);
-				ensure!(drp < 3650, Error::<T>::DefaultReturnPolicyIsExcessive);
+			if let Some(defaultreturnpolicy) = info.defaultreturnpolicy {
+				ensure!(defaultreturnpolicy < 3650, Error::<T>::DefaultReturnPolicyIsExcessive);
 			}
 			// check for optional seller account (when the signer acts as a proxy for gasless
 			// transactions)
-			let selleraccount =
-				json_get_value(configuration.clone(), "selleraccount".as_bytes().to_vec());
-			if !selleraccount.is_empty() {
-				let selleraccountv = bs58::decode(selleraccount).into_vec().unwrap();
-				let selleraccountid = T::AccountId::decode(&mut &selleraccountv[1..33]).unwrap();
-				sender = selleraccountid;
+			if let Some(selleraccount) = selleraccount {
+				sender = selleraccount;
 			}
-			//store seller on chain
-			if !Sellers::<T>::contains_key(&sender) {
-				// Insert new seller
-				Sellers::<T>::insert(sender.clone(), configuration.clone());
-			} else {
-				// check for proxy account before updating
-				if originalsigner != sender {
-					let settings = Sellers::<T>::get(sender.clone());
-					let proxyaccount =
-						json_get_value(settings.clone(), "proxyaccount".as_bytes().to_vec());
-					ensure!(!proxyaccount.is_empty(), Error::<T>::SignerIsNotAuthorized);
-					let proxyaccountv = bs58::decode(proxyaccount).into_vec().unwrap();
-					let proxyaccountid = T::AccountId::decode(&mut &proxyaccountv[1..33]).unwrap();
-					ensure!(proxyaccountid == originalsigner, Error::<T>::SignerIsNotAuthorized);
+			// check for proxy account before updating, a new seller can be created only from
+			// its own account
+			if originalsigner != sender {
+				let settings =
+					Sellers::<T>::get(&sender).ok_or(Error::<T>::SignerIsNotAuthorized)?;
+				ensure!(
+					settings.proxyaccount == Some(originalsigner),
+					Error::<T>::SignerIsNotAuthorized
+				);
+			}
+			// reserve the deposit for the data stored, adjusting the one of the previous data
+			let deposit = Self::deposit_for(info.encoded_size());
+			Self::update_deposit(&sender, SellerDeposits::<T>::get(&sender), deposit)?;
+			SellerDeposits::<T>::insert(&sender, deposit);
+			// count the references to the reference data
+			let oldreferences =
+				Sellers::<T>::get(&sender).map(|old| old.references()).unwrap_or_default();
+			let oldcount = oldreferences.len() as u32;
+			let newreferences = info.references();
+			let newcount = newreferences.len() as u32;
+			Self::update_references(oldreferences, newreferences);
+			// remove the verifications of the data changed
+			if let Some(old) = Sellers::<T>::get(&sender) {
+				for (index, certification) in old.certifications.iter().enumerate() {
+					if info.certifications.get(index) != Some(certification) {
+						CertificationAttestations::<T>::remove(&sender, index as u32);
+					}
+				}
+				if old.sellertype != info.sellertype ||
+					old.name != info.name ||
+					old.address != info.address
+				{
+					SellerKyc::<T>::remove(&sender);
 				}
-				// Replace Seller Data
-				Sellers::<T>::take(sender.clone());
-				Sellers::<T>::insert(sender.clone(), configuration.clone());
 			}
+			//store seller on chain
+			Sellers::<T>::insert(sender.clone(), info.clone());
 			// Generate event
-			Self::deposit_event(Event::MarketPlaceSellerCreated(sender, configuration));
-			// Return a successful DispatchResult
-			Ok(())
+			Self::deposit_event(Event::MarketPlaceSellerCreated(sender, info));
+			// Return the weight of the references counted
+			Ok(Some(T::WeightInfo::create_update_seller(newcount, oldcount)).into())
 		}
 		/// Destroy a Seller
 		#[pallet::call_index(9)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn destroy_seller(origin: OriginFor<T>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::destroy_seller(MAX_SELLER_REFERENCES))]
+		pub fn destroy_seller(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			// check the request is signed
 			let sender = ensure_signed(origin)?;
 			// verify the seller exists
-			ensure!(Sellers::<T>::contains_key(&sender), Error::<T>::SellerDataNotFound);
-			// Remove Seller
+			let info = Sellers::<T>::get(&sender).ok_or(Error::<T>::SellerDataNotFound)?;
+			// Remove Seller, its references, its verifications and return its deposit
 			Sellers::<T>::take(sender.clone());
+			let references = info.references();
+			let referencescount = references.len() as u32;
+			Self::update_references(references, Vec::new());
+			let _ = CertificationAttestations::<T>::clear_prefix(&sender, u32::MAX, None);
+			SellerKyc::<T>::remove(&sender);
+			T::Currency::unreserve(&sender, SellerDeposits::<T>::take(&sender));
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
+			//the products of the seller are left on chain
 			Self::deposit_event(Event::MarketPlaceSellerDestroyed(sender));
-			// Return a successful DispatchResult
-			Ok(())
+			// Return the weight of the references removed
+			Ok(Some(T::WeightInfo::destroy_seller(referencescount)).into())
 		}
 		/// Create/update a Product
-		/// Example:
-		/// {"description":"xxxx","longdescription","xxxx","price":1000,"currencycode","USDC"}
+		/// the optional (department, category) is used to index the product
 		#[pallet::call_index(10)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
+		#[pallet::weight(T::WeightInfo::create_update_product(
+			info.references().len() as u32,
+			MAX_PRODUCT_REFERENCES
+		))]
 		pub fn create_update_product(
 			origin: OriginFor<T>,
 			uid: u32,
-			configuration: Vec<u8>,
-		) -> DispatchResult {
+			info: ProductInfo,
+		) -> DispatchResultWithPostInfo {
 			// check the request is signed
-			let _sender = ensure_signed(origin)?;
-			//check configuration length
-			ensure!(configuration.len() > 12, Error::<T>::ConfigurationTooShort);
-			ensure!(configuration.len() < 65536, Error::<T>::ConfigurationTooLong);
-			// check json validity
-			ensure!(json_check_validity(configuration.clone()), Error::<T>::InvalidJson);
-			// check for mandatory short description
-			let description =
-				json_get_value(configuration.clone(), "description".as_bytes().to_vec());
-			ensure!(description.len() >= 10, Error::<T>::ProductDescriptionTooShort);
-			ensure!(description.len() <= 64, Error::<T>::ProductDescriptionTooLong);
-			// check for mandatory long description
-			let longdescription =
-				json_get_value(configuration.clone(), "longdescription".as_bytes().to_vec());
-			ensure!(longdescription.len() >= 64, Error::<T>::ProductLongDescriptionTooShort);
-			ensure!(longdescription.len() <= 8192, Error::<T>::ProductLongDescriptionTooLong);
-			// check for price >0
-			let price = json_get_value(configuration.clone(), "price".as_bytes().to_vec());
-			let pricevalue = vecu8_to_u128(price);
-			ensure!(pricevalue > 0, Error::<T>::ProductPriceCannotBeZero);
-			// check for mandatory currency code
-			let currencycode =
-				json_get_value(configuration.clone(), "currency".as_bytes().to_vec());
-			ensure!(Currencies::<T>::contains_key(&currencycode), Error::<T>::CurrencyCodeNotFound);
-			// check for specifications
-			let specifications =
-				json_get_value(configuration.clone(), "specifications".as_bytes().to_vec());
-			ensure!(
-				(specifications.is_empty() || specifications.len() >= 8192),
-				Error::<T>::SpecificationsIsdInvalid
-			);
-			// Media is an array of photos,videos and document being part of the product
-			// documentation the structure can be
-			// [{"description":"xxxxx","filename":"xxxxxxx"},"ipfs":"xxxxxxx",("color":xx)},{..}]
-			let media = json_get_complexarray(configuration.clone(), "media".as_bytes().to_vec());
-			ensure!(media.len() > 10, Error::<T>::MediaCannotBeEmpty);
-			let mut x = 0;
-			loop {
-				let jr = json_get_recordvalue(media.clone(), x);
-				if jr.is_empty() {
-					break
-				}
-				let description = json_get_value(jr.clone(), "description".as_bytes().to_vec());
-				ensure!(!description.is_empty(), Error::<T>::MediaDescriptionIswrong);
-
-				let filename = json_get_value(jr.clone(), "filename".as_bytes().to_vec());
-				ensure!(!filename.is_empty(), Error::<T>::MediaFileNameIsWrong);
-
-				let ipfs = json_get_value(jr.clone(), "ipfs".as_bytes().to_vec());
-				ensure!(ipfs.len() >= 32, Error::<T>::MediaIpfsAddressIsWrong);
-				let color = json_get_value(jr.clone(), "color".as_bytes().to_vec());
-				if !color.is_empty() {
-					let colorvalue = vecu8_to_u32(color);
-					ensure!(
-						ProductColors::<T>::contains_key(colorvalue),
-						Error::<T>::ColorNotFound
-					);
-				}
-				x += 1;
-			}
-			// check colors if enabled
-			let colors = json_get_complexarray(configuration.clone(), "colors".as_bytes().to_vec());
-			if !colors.is_empty() {
-				x = 0;
-				loop {
-					let c = json_get_arrayvalue(colors.clone(), x);
-					if c.is_empty() {
-						break
-					}
-					let cv = vecu8_to_u32(c);
-					ensure!(ProductColors::<T>::contains_key(cv), Error::<T>::ColorNotFound);
-					x += 1;
-				}
-			}
-			// check size if enabled
-			let sizes = json_get_complexarray(configuration.clone(), "sizes".as_bytes().to_vec());
-			if !sizes.is_empty() {
-				x = 0;
-				loop {
-					let sz = json_get_arrayvalue(sizes.clone(), x);
-					if sz.is_empty() {
-						break
-					}
-					let szv = vecu8_to_u32(sz);
-					ensure!(ProductSizes::<T>::contains_key(szv), Error::<T>::ColorNotFound);
-					x += 1;
-				}
-			}
-			// check dimension (optional)
-			let dimension =
-				json_get_complexarray(configuration.clone(), "dimension".as_bytes().to_vec());
-			if !dimension.is_empty() {
-				x = 0;
-				loop {
-					let jr = json_get_recordvalue(dimension.clone(), x);
-					if !jr.is_empty() {
-						break
-					}
-					// check for length
-					let l = json_get_value(jr.clone(), "length".as_bytes().to_vec());
-					let v = vecu8_to_u32(l);
-					ensure!(v > 0, Error::<T>::DimensionWrongLength);
-					// check for wide
-					let w = json_get_value(jr.clone(), "wide".as_bytes().to_vec());
-					let v = vecu8_to_u32(w);
-					ensure!(v > 0, Error::<T>::DimensionWrongWide);
-					// check for height
-					let h = json_get_value(jr.clone(), "height".as_bytes().to_vec());
-					let v = vecu8_to_u32(h);
-					ensure!(v > 0, Error::<T>::DimensionWrongHeight);
-					// check for Weight
-					let w = json_get_value(jr.clone(), "weight".as_bytes().to_vec());
-					let v = vecu8_to_u32(w);
-					ensure!(v > 0, Error::<T>::DimensionWrongWeight);
-					x += 1;
+			let sender = ensure_signed(origin)?;
+			// validate and store the product
+			let newcount = info.references().len() as u32;
+			let oldcount = Self::do_upsert_product(sender, uid, info)?;
+			// Return the weight of the references counted
+			Ok(Some(T::WeightInfo::create_update_product(newcount, oldcount)).into())
+		}
+		/// Create or update a batch of products (uid, data) of the seller, every product is
+		/// validated with the rules of create_update_product. The products refused don't revert
+		/// the batch, an event reports the error of each of them.
+		/// The weight is the one of create_update_product for every product, the references of
+		/// the previous data of the products updated are counted at most MAX_PRODUCT_REFERENCES
+		/// per product and refunded after the execution.
+		#[pallet::call_index(72)]
+		#[pallet::weight(T::WeightInfo::batch_upsert_products(
+			products.len() as u32,
+			products.iter().map(|(_, info)| info.references().len() as u32).sum(),
+			(products.len() as u32).saturating_mul(MAX_PRODUCT_REFERENCES),
+		))]
+		pub fn batch_upsert_products(
+			origin: OriginFor<T>,
+			products: BoundedVec<(u32, ProductInfo), T::MaxProductsBatch>,
+		) -> DispatchResultWithPostInfo {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// check the size of the batch
+			ensure!(!products.is_empty(), Error::<T>::ProductsBatchCannotBeEmpty);
+			// store every product in its own storage layer, reverted when the product is refused
+			let total = products.len() as u32;
+			let mut stored: u32 = 0;
+			let mut newcount: u32 = 0;
+			let mut oldcount: u32 = 0;
+			for (uid, info) in products.into_iter() {
+				newcount = newcount.saturating_add(info.references().len() as u32);
+				match with_storage_layer(|| Self::do_upsert_product(sender.clone(), uid, info)) {
+					Ok(count) => {
+						stored += 1;
+						oldcount = oldcount.saturating_add(count);
+					},
+					Err(error) => {
+						Self::deposit_event(Event::MarketPlaceProductRefused(uid, error));
+					},
 				}
 			}
-			// check UPC code (TODO - UPC check CRC validity)
-			let u = json_get_value(configuration.clone(), "upc".as_bytes().to_vec());
-			ensure!(u.len() > 7, Error::<T>::UniversalProductCodeIsWrong);
-			// check for shipping countries (optional)
-			let shippingcountries = json_get_complexarray(
-				configuration.clone(),
-				"shippingcountries".as_bytes().to_vec(),
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductsBatchStored(sender, total, stored));
+			// Return the weight of the references counted
+			Ok(Some(T::WeightInfo::batch_upsert_products(total, newcount, oldcount)).into())
+		}
+		/// Destroy a Product, only the seller who created it can remove it
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::destroy_product(
+			MAX_PRODUCT_REFERENCES,
+			MAX_STOCK_VARIANTS,
+			MAX_SUBSCRIBERS
+		))]
+		pub fn destroy_product(origin: OriginFor<T>, uid: u32) -> DispatchResultWithPostInfo {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// verify the product exists
+			let info = Products::<T>::get(uid).ok_or(Error::<T>::ProductNotFound)?;
+			// check the signer is the owner of the product
+			ensure!(
+				ProductOwner::<T>::get(uid) == Some(sender.clone()),
+				Error::<T>::SignerIsNotAuthorized
 			);
-			if !shippingcountries.is_empty() {
-				x = 0;
-				loop {
-					let countrycode = json_get_arrayvalue(shippingcountries.clone(), x);
-					if countrycode.is_empty() {
-						break
-					}
-					ensure!(
-						!IsoCountries::<T>::contains_key(&countrycode),
-						Error::<T>::CountryCodeNotFound
-					);
-					x += 1;
-				}
-			}
-			// check for shipping area
-			// TODO (check for GPS coordinates validity)
-			let shippingarea =
-				json_get_complexarray(configuration.clone(), "shippingarea".as_bytes().to_vec());
-			if !shippingarea.is_empty() {
-				x = 0;
-				loop {
-					let centerlatitude =
-						json_get_value(shippingarea.clone(), "centerlatitude".as_bytes().to_vec());
-					let centerlongitude =
-						json_get_value(shippingarea.clone(), "centerlongitude".as_bytes().to_vec());
-					let borderlatitude =
-						json_get_value(shippingarea.clone(), "borderlatitude".as_bytes().to_vec());
-					let borderlongitude =
-						json_get_value(shippingarea.clone(), "borderlongitude".as_bytes().to_vec());
-					ensure!(!centerlatitude.len() > 0, Error::<T>::CenterLatitudeIsMissing);
-					ensure!(!centerlongitude.len() > 0, Error::<T>::CenterLongitudeIsMissing);
-					ensure!(!borderlatitude.len() > 0, Error::<T>::BorderLatitudeIsMissing);
-					ensure!(!borderlongitude.len() > 0, Error::<T>::BorderLongitudeIsMissing);
-					x += 1;
-				}
-			}
-			// check for the shippers (optional field)
-			let shippers = json_get_value(configuration.clone(), "shippers".as_bytes().to_vec());
-			if !shippers.is_empty() {
-				x = 0;
-				loop {
-					let shipper = json_get_arrayvalue(shippers.clone(), x);
-					let v = vecu8_to_u32(shipper);
-					ensure!(Shippers::<T>::contains_key(v), Error::<T>::ShipperNotFound);
-					x += 1;
-				}
-			}
-			// check for the API (optional field)
-			let apiavailability =
-				json_get_value(configuration.clone(), "apiavailability".as_bytes().to_vec());
-			if !apiavailability.is_empty() {
-				ensure!(aisland_validate_weburl(apiavailability), Error::<T>::InvalidApiUrl);
-			}
-			// check for the language if any
-			let language = json_get_value(configuration.clone(), "language".as_bytes().to_vec());
-			if !language.is_empty() {
-				ensure!(aisland_validate_languagecode(language), Error::<T>::LanguageCodeIsWrong);
-			}
-			// TODO check the products was created from the same signer
-			if Products::<T>::contains_key(uid) {
-				Products::<T>::take(uid);
-			}
-			Products::<T>::insert(uid, configuration.clone());
+			// Remove product, its owner, its status, the indexes, the stock and the subscriptions,
+			// the billings scheduled are skipped
+			Products::<T>::take(uid);
+			ProductOwner::<T>::take(uid);
+			ProductStatuses::<T>::remove(uid);
+			Self::remove_product_indexes(uid, &sender, &info);
+			let references = info.references();
+			let referencescount = references.len() as u32;
+			Self::update_references(references, Vec::new());
+			let variants = Stock::<T>::clear_prefix((uid,), MAX_STOCK_VARIANTS, None).unique;
+			SubscriptionPlans::<T>::remove(uid);
+			let subscribers = Subscriptions::<T>::clear_prefix(uid, MAX_SUBSCRIBERS, None).unique;
+			SubscriberCount::<T>::remove(uid);
+			// return the deposit
+			T::Currency::unreserve(&sender, ProductDeposits::<T>::take(uid));
 			// Generate event
-			Self::deposit_event(Event::MarketPlaceProductUpdated(uid, configuration));
-			// Return a successful DispatchResult
-			Ok(())
+			Self::deposit_event(Event::MarketPlaceProductDestroyed(uid));
+			// Return the weight of the references, the stock and the subscriptions removed
+			Ok(Some(T::WeightInfo::destroy_product(referencescount, variants, subscribers)).into())
 		}
 		#[pallet::call_index(11)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
@@ -1107,19 +1819,20 @@ pub mod pallet {
 			countrycode: Vec<u8>,
 			dialcode: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the countries
+			Self::ensure_curator(origin, CuratorDomain::Countries)?;
 			// check country code length == 2
 			ensure!(countrycode.len() == 2, Error::<T>::WrongLengthCountryCode);
-			// check country name length  >= 3
+			let code: CountryCode =
+				countrycode.clone().try_into().map_err(|_| Error::<T>::WrongLengthCountryCode)?;
+			// check dial code length  >= 2
 			ensure!(dialcode.len() >= 2, Error::<T>::DialcodeTooShort);
+			let dial: ShortText =
+				dialcode.clone().try_into().map_err(|_| Error::<T>::DialcodeTooLong)?;
 			// check the dialcode is not alreay present on chain
-			ensure!(
-				!IsoDialcode::<T>::contains_key(&countrycode),
-				Error::<T>::CountryCodeAlreadyPresent
-			);
+			ensure!(!IsoDialcode::<T>::contains_key(&code), Error::<T>::CountryCodeAlreadyPresent);
 			// store the Iso Dial Code
-			IsoDialcode::<T>::insert(countrycode.clone(), dialcode.clone());
+			IsoDialcode::<T>::insert(code, dial);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceIsoDialCodeCreated(countrycode, dialcode));
 			// Return a successful DispatchResult
@@ -1132,73 +1845,61 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			countrycode: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the countries
+			Self::ensure_curator(origin, CuratorDomain::Countries)?;
 			// verify the country code exists
-			ensure!(IsoDialcode::<T>::contains_key(&countrycode), Error::<T>::CountryCodeNotFound);
+			let code: CountryCode =
+				countrycode.clone().try_into().map_err(|_| Error::<T>::CountryCodeNotFound)?;
+			ensure!(IsoDialcode::<T>::contains_key(&code), Error::<T>::CountryCodeNotFound);
 			// Remove country code
-			IsoDialcode::<T>::take(countrycode.clone());
+			IsoDialcode::<T>::take(code);
 			// Generate event
 			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceIsoDialCodeDestroyed(countrycode));
 			// Return a successful DispatchResult
 			Ok(())
 		}
-		/// Create a new Currency code with name and other info in a json structure
-		/// {"name":"Bitcoin","category":"c(rypto)/f(iat)","country":"countryisocode","blockchain":"
-		/// Ethereum(...)","address":"xxxfor_crypto_currencyxxx"} for example:
-		/// {"name":"Bitcoin","category":"c","country":"AE","blockchain":"Bitcoin","address":"not
-		/// applicable"} {"name":"American Dollars","category":"f","country":"US","blockchain":"not
-		/// applicable","address":"not applicable"}
+		/// Create a new Currency code with name and other info, the country is required for the
+		/// fiat currencies (like USD) and the blockchain for the crypto currencies (like Bitcoin)
 		#[pallet::call_index(14)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn create_currency(
 			origin: OriginFor<T>,
 			currencycode: Vec<u8>,
-			info: Vec<u8>,
+			info: CurrencyInfo,
 		) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the currencies
+			Self::ensure_curator(origin, CuratorDomain::Currencies)?;
 			// check currency code length is between 3 and 5 bytes
-			ensure!(
-				(currencycode.len() >= 3 && currencycode.len() <= 5),
-				Error::<T>::WrongLengthCurrencyCode
-			);
-			// check the info field is not longer 1024 bytes
-			ensure!((info.len() <= 1024), Error::<T>::SizeInfoTooLong);
-			// check for a valid json structure
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
+			ensure!(currencycode.len() >= 3, Error::<T>::WrongLengthCurrencyCode);
+			let code: CurrencyCode = currencycode
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::WrongLengthCurrencyCode)?;
 			// check for name
-			let name = json_get_value(info.clone(), "name".as_bytes().to_vec());
-			ensure!(name.len() >= 3, Error::<T>::CurrencyNameTooShort);
-			ensure!(name.len() <= 32, Error::<T>::CurrencyNameTooLong);
-			// check for type of currency (fiat/crypto)
-			let category = json_get_value(info.clone(), "category".as_bytes().to_vec());
-			let c: Vec<u8> = vec![b'c'];
-			let f: Vec<u8> = vec![b'f'];
-
-			ensure!((category == c || category == f), Error::<T>::CurrencyCategoryIswrong);
-			// check for the country code in case of Fiat currency
-			if category == f {
-				let countrycode = json_get_value(info.clone(), "country".as_bytes().to_vec());
-				ensure!(
-					IsoCountries::<T>::contains_key(countrycode),
-					Error::<T>::CountryCodeNotFound
-				);
-			}
-			// check for the blockchain in case of Crypto currency
-			if category == c {
-				let blockchain = json_get_value(info.clone(), "blockchain".as_bytes().to_vec());
-				ensure!(blockchain.len() >= 3, Error::<T>::BlockchainNameTooShort);
-				ensure!(blockchain.len() <= 32, Error::<T>::BlockchainNameTooLong);
+			ensure!(info.name.len() >= 3, Error::<T>::CurrencyNameTooShort);
+			ensure!(info.name.len() <= 32, Error::<T>::CurrencyNameTooLong);
+			match info.category {
+				// check for the country code in case of Fiat currency
+				CurrencyCategory::Fiat => {
+					let country = info.country.clone().ok_or(Error::<T>::CountryCodeNotFound)?;
+					ensure!(
+						IsoCountries::<T>::contains_key(country),
+						Error::<T>::CountryCodeNotFound
+					);
+				},
+				// check for the blockchain in case of Crypto currency
+				CurrencyCategory::Crypto => {
+					let blockchain = info.blockchain.clone().unwrap_or_default();
+					ensure!(blockchain.len() >= 3, Error::<T>::BlockchainNameTooShort);
+					ensure!(blockchain.len() <= 32, Error::<T>::BlockchainNameTooLong);
+				},
 			}
 			// check the currency is not alreay present on chain
-			ensure!(
-				!Currencies::<T>::contains_key(&currencycode),
-				Error::<T>::CurrencyCodeAlreadyPresent
-			);
+			ensure!(!Currencies::<T>::contains_key(&code), Error::<T>::CurrencyCodeAlreadyPresent);
 			// store the Currency Code and info
-			Currencies::<T>::insert(currencycode.clone(), info.clone());
+			Self::update_references(Vec::new(), info.references());
+			Currencies::<T>::insert(code, info.clone());
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceCurrencyCodeCreated(currencycode, info));
 			// Return a successful DispatchResult
@@ -1208,14 +1909,19 @@ pub mod pallet {
 		#[pallet::call_index(15)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_currency(origin: OriginFor<T>, currencycode: Vec<u8>) -> DispatchResult {
-			// check the request is signed from the Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the currencies
+			Self::ensure_curator(origin, CuratorDomain::Currencies)?;
 			// verify the currency code exists
-			ensure!(Currencies::<T>::contains_key(&currencycode), Error::<T>::CurrencyCodeNotFound);
-			// Remove currency code
-			Currencies::<T>::take(currencycode.clone());
+			let code: CurrencyCode =
+				currencycode.clone().try_into().map_err(|_| Error::<T>::CurrencyCodeNotFound)?;
+			let info = Currencies::<T>::get(&code).ok_or(Error::<T>::CurrencyCodeNotFound)?;
+			// check the currency is not in use
+			Self::ensure_not_in_use(ReferenceKey::Currency(code.clone()))?;
+			// Remove currency code, its asset and its references
+			CurrencyAssets::<T>::remove(&code);
+			Currencies::<T>::take(code);
+			Self::update_references(info.references(), Vec::new());
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceCurrencyDestroyed(currencycode));
 			// Return a successful DispatchResult
 			Ok(())
@@ -1228,17 +1934,21 @@ pub mod pallet {
 			uid: u32,
 			description: Vec<u8>,
 		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::ColorUidCannotBeZero);
 			//check description length
 			ensure!(description.len() >= 2, Error::<T>::ColorDescriptionTooShort);
 			ensure!(description.len() < 32, Error::<T>::ColorDescriptionTooLong);
+			let text: ShortText = description
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::ColorDescriptionTooLong)?;
 			// check the color is not alreay present on chain
 			ensure!(!ProductColors::<T>::contains_key(uid), Error::<T>::ColorAlreadyPresent);
 			// store the color
-			ProductColors::<T>::insert(uid, description.clone());
+			ProductColors::<T>::insert(uid, text);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceColorCreated(uid, description));
 			// Return a successful DispatchResult
@@ -1248,53 +1958,37 @@ pub mod pallet {
 		#[pallet::call_index(17)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_product_color(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// verify the color exists
 			ensure!(ProductColors::<T>::contains_key(uid), Error::<T>::ColorNotFound);
+			// check the color is not in use
+			Self::ensure_not_in_use(ReferenceKey::Color(uid))?;
 			// Remove color
 			ProductColors::<T>::take(uid);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceColorDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
 		}
-		/// Create a new product Size
-		/// example json in info field: {"code":"XL","description":"Extra Large","area":"Europe"}
+		/// Create a new product Size table, for example with a single size:
+		/// code "XL", description "Extra Large", area "Europe"
 		#[pallet::call_index(18)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn create_product_size(
 			origin: OriginFor<T>,
 			uid: u32,
-			info: Vec<u8>,
+			info: SizeTable,
 		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::SizeUidCannotBeZero);
-			//check info length
-			ensure!(info.len() < 8192, Error::<T>::SizeInfoTooLong);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
-			// checking sizes structure that must have some fields defined
-			let mut x = 0;
-			loop {
-				let sz = json_get_recordvalue(info.clone(), x);
-				if sz.is_empty() {
-					break
-				}
-
-				let code = json_get_value(info.clone(), "code".as_bytes().to_vec());
-				ensure!(!code.is_empty(), Error::<T>::SizeCodeIsMissing);
-
-				let description = json_get_value(info.clone(), "description".as_bytes().to_vec());
-				ensure!(!description.is_empty(), Error::<T>::SizeDescriptionIsMissing);
-
-				let area = json_get_value(info.clone(), "area".as_bytes().to_vec());
-				ensure!(!area.is_empty(), Error::<T>::SizeAreaIsMissing);
-
-				x += 1;
+			// checking sizes structure that must have all the fields set
+			for sz in info.iter() {
+				ensure!(!sz.code.is_empty(), Error::<T>::SizeCodeIsMissing);
+				ensure!(!sz.description.is_empty(), Error::<T>::SizeDescriptionIsMissing);
+				ensure!(!sz.area.is_empty(), Error::<T>::SizeAreaIsMissing);
 			}
 			// check the size is not alreay present on chain
 			ensure!(!ProductSizes::<T>::contains_key(uid), Error::<T>::SizeAlreadyPresent);
@@ -1309,41 +2003,36 @@ pub mod pallet {
 		#[pallet::call_index(19)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_product_size(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the categories
+			Self::ensure_curator(origin, CuratorDomain::Categories)?;
 			// verify the size exists
 			ensure!(ProductSizes::<T>::contains_key(uid), Error::<T>::SizeNotFound);
+			// check the size is not in use
+			Self::ensure_not_in_use(ReferenceKey::Size(uid))?;
 			// Remove size
 			ProductSizes::<T>::take(uid);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceSizeDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
 		}
 		/// Create a new Manufacturer
-		/// Example field info: {"name":"Samsung","website":"https://www.samsung.com"}
+		/// Example: name "Samsung", website "https://www.samsung.com"
 		#[pallet::call_index(20)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn create_manufacturer(
 			origin: OriginFor<T>,
 			uid: u32,
-			info: Vec<u8>,
+			info: ManufacturerInfo,
 		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::ManufacturerUidCannotBeZero);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
 			// check for name field
-			let name = json_get_value(info.clone(), "name".as_bytes().to_vec());
-			ensure!(name.len() >= 4, Error::<T>::ManufacturerNameIsTooShort);
-			ensure!(name.len() <= 64, Error::<T>::ManufacturerNameIsTooLong);
+			ensure!(info.name.len() >= 4, Error::<T>::ManufacturerNameIsTooShort);
 			// check for website field
-			let website = json_get_value(info.clone(), "website".as_bytes().to_vec());
-			ensure!(website.len() >= 4, Error::<T>::ManufacturerWebsiteIsTooShort);
-			ensure!(website.len() <= 64, Error::<T>::ManufacturerWebsiteIsTooLong);
+			ensure!(info.website.len() >= 4, Error::<T>::ManufacturerWebsiteIsTooShort);
 			// check the manufacturer is not alreay present on chain
 			ensure!(!Manufacturers::<T>::contains_key(uid), Error::<T>::ManufacturerAlreadyPresent);
 			// store the manufacturer
@@ -1357,77 +2046,47 @@ pub mod pallet {
 		#[pallet::call_index(21)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_manufacturer(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// verify the manufacturer exists
 			ensure!(Manufacturers::<T>::contains_key(uid), Error::<T>::ManufacturerNotFound);
+			// check the manufacturer is not in use
+			Self::ensure_not_in_use(ReferenceKey::Manufacturer(uid))?;
 			// Remove manufacturer
 			Manufacturers::<T>::take(uid);
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceManufacturerDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
 		}
 		/// Create a new Shipper
-		/// exmaple info field: {"name":"DHL","website":"www.dhl.com"}
+		/// exmaple: name "DHL", website "www.dhl.com"
 		#[pallet::call_index(22)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn create_shipper(origin: OriginFor<T>, uid: u32, info: Vec<u8>) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+		#[pallet::weight(T::WeightInfo::create_shipper(info.references().len() as u32))]
+		pub fn create_shipper(origin: OriginFor<T>, uid: u32, info: ShipperInfo) -> DispatchResult {
+			// check the request is signed from a curator of the shipping
+			Self::ensure_curator(origin, CuratorDomain::Shipping)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::ShipperUidCannotBeZero);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
-			ensure!(info.len() <= 16384, Error::<T>::InvalidJson);
 			// check for name field
-			let name = json_get_value(info.clone(), "name".as_bytes().to_vec());
-			ensure!(name.len() >= 3, Error::<T>::ShipperNameIsTooShort);
-			ensure!(name.len() <= 64, Error::<T>::ShipperNameIsTooLong);
-			// check for info field
-			ensure!(info.len() <= 32768, Error::<T>::JsonIsTooLong);
+			ensure!(info.name.len() >= 3, Error::<T>::ShipperNameIsTooShort);
 			// check for website field
-			let website = json_get_value(info.clone(), "website".as_bytes().to_vec());
-			ensure!(website.len() >= 4, Error::<T>::ShipperWebsiteIsTooShort);
-			ensure!(website.len() <= 64, Error::<T>::ShipperWebsiteIsTooLong);
+			ensure!(info.website.len() >= 4, Error::<T>::ShipperWebsiteIsTooShort);
 			// check the Shipper is not alreay present on chain
 			ensure!(!Shippers::<T>::contains_key(uid), Error::<T>::ShipperAlreadyPresent);
 			// check for origincountries field (optional)
-			let origincountries =
-				json_get_complexarray(info.clone(), "origincountries".as_bytes().to_vec());
-			if !origincountries.is_empty() {
-				let mut x = 0;
-				loop {
-					let oc = json_get_arrayvalue(origincountries.clone(), x);
-					if oc.is_empty() {
-						break
-					}
-					ensure!(
-						IsoCountries::<T>::contains_key(oc),
-						Error::<T>::OriginCountryNotPresent
-					);
-					x += 1;
-				}
+			for oc in info.origincountries.iter() {
+				ensure!(IsoCountries::<T>::contains_key(oc), Error::<T>::OriginCountryNotPresent);
 			}
 			// check for destinationcountries field (optional)
-			let destinationcountries =
-				json_get_complexarray(info.clone(), "destinationcountries".as_bytes().to_vec());
-			if !destinationcountries.is_empty() {
-				let mut x = 0;
-				loop {
-					let dc = json_get_arrayvalue(destinationcountries.clone(), x);
-					if dc.is_empty() {
-						break
-					}
-					ensure!(
-						IsoCountries::<T>::contains_key(dc),
-						Error::<T>::DestinationCountryNotPresent
-					);
-					x += 1;
-				}
+			for dc in info.destinationcountries.iter() {
+				ensure!(
+					IsoCountries::<T>::contains_key(dc),
+					Error::<T>::DestinationCountryNotPresent
+				);
 			}
 			// store the shippers
+			Self::update_references(Vec::new(), info.references());
 			Shippers::<T>::insert(uid, info.clone());
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceShipperCreated(uid, info));
@@ -1436,129 +2095,119 @@ pub mod pallet {
 		}
 		/// Destroy a Shipper
 		#[pallet::call_index(23)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn destroy_shipper(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+		#[pallet::weight(T::WeightInfo::destroy_shipper(MAX_SHIPPER_REFERENCES))]
+		pub fn destroy_shipper(origin: OriginFor<T>, uid: u32) -> DispatchResultWithPostInfo {
+			// check the request is signed from a curator of the shipping
+			Self::ensure_curator(origin, CuratorDomain::Shipping)?;
 			// verify the shipper exists
-			ensure!(Shippers::<T>::contains_key(uid), Error::<T>::ShipperNotFound);
-			// Remove shipper
+			let info = Shippers::<T>::get(uid).ok_or(Error::<T>::ShipperNotFound)?;
+			// check the shipper is not in use
+			Self::ensure_not_in_use(ReferenceKey::Shipper(uid))?;
+			// Remove shipper and its references
 			Shippers::<T>::take(uid);
+			let references = info.references();
+			let referencescount = references.len() as u32;
+			Self::update_references(references, Vec::new());
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceShipperDestroyed(uid));
-			// Return a successful DispatchResult
-			Ok(())
+			// Return the weight of the references removed
+			Ok(Some(T::WeightInfo::destroy_shipper(referencescount)).into())
 		}
-		/// Create new Shipping Rates
-		/// example field info:
-		/// {"shipperid":1,"origincountry":"AE","currency":"AED","rates":[{"destination":"LR","
-		/// fromkg":0,"to":1,"rate":10},{"destination":"LR","fromkg":1,"tokg":2,"rate":15}]}
+		/// Create new Shipping Rates of a shipper from the origin country, every rate is valid for
+		/// a destination country and a weight between "fromkg" and "tokg"
 		#[pallet::call_index(24)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
+		#[pallet::weight(T::WeightInfo::create_shipping_rates(
+			info.references().len() as u32,
+			MAX_SHIPPING_RATE_REFERENCES
+		))]
 		pub fn create_shipping_rates(
 			origin: OriginFor<T>,
 			uid: u32,
-			info: Vec<u8>,
-		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			info: ShippingRateTable,
+		) -> DispatchResultWithPostInfo {
+			// check the request is signed from a curator of the shipping
+			Self::ensure_curator(origin, CuratorDomain::Shipping)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::ShippingRateUidCannotBeZero);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
-			// check for info field
-			ensure!(info.len() <= 32768, Error::<T>::JsonIsTooLong);
 			// check for shipperid field
-			let shipperid = json_get_value(info.clone(), "shipperid".as_bytes().to_vec());
-			let shipperidv = vecu8_to_u32(shipperid);
-			ensure!(shipperidv > 0, Error::<T>::ShipperIdIsMissing);
-			ensure!(Shippers::<T>::contains_key(shipperidv), Error::<T>::ShipperNotFound);
+			ensure!(info.shipperid > 0, Error::<T>::ShipperIdIsMissing);
+			ensure!(Shippers::<T>::contains_key(info.shipperid), Error::<T>::ShipperNotFound);
 			// check for origincountry field
-			let origincountry = json_get_value(info.clone(), "origincountry".as_bytes().to_vec());
 			ensure!(
-				IsoCountries::<T>::contains_key(origincountry.clone()),
+				IsoCountries::<T>::contains_key(&info.origincountry),
 				Error::<T>::OriginCountryNotPresent
 			);
 			// check for currency field
-			let currency = json_get_value(info.clone(), "currency".as_bytes().to_vec());
-			ensure!(Currencies::<T>::contains_key(currency), Error::<T>::CurrencyCodeNotFound);
+			ensure!(
+				Currencies::<T>::contains_key(&info.currency),
+				Error::<T>::CurrencyCodeNotFound
+			);
 			// check for rates
-			let rates = json_get_complexarray(info.clone(), "rates".as_bytes().to_vec());
-			if !rates.is_empty() {
-				let mut x = 0;
-				loop {
-					let r = json_get_recordvalue(rates.clone(), x);
-					if r.is_empty() {
-						break
-					}
-					// check for destination
-					let dc = json_get_value(r.clone(), "destination".as_bytes().to_vec());
-					ensure!(
-						IsoCountries::<T>::contains_key(dc),
-						Error::<T>::OriginCountryNotPresent
-					);
-					// check for fromkg
-					let fromkg = json_get_value(r.clone(), "fromkg".as_bytes().to_vec());
-					ensure!(!fromkg.is_empty(), Error::<T>::FromKgIsMissing);
-					// check for tokg
-					let tokg = json_get_value(r.clone(), "tokg".as_bytes().to_vec());
-					ensure!(!tokg.is_empty(), Error::<T>::ToKgIsMissing);
-					// check for rates  (in the currency set)
-					let rate = json_get_value(r.clone(), "rate".as_bytes().to_vec());
-					let ratev = vecu8_to_u32(rate);
-					ensure!(ratev > 0, Error::<T>::ShippingRateCannotbeZero);
-					x += 1;
-				}
+			for r in info.rates.iter() {
+				// check for destination
+				ensure!(
+					IsoCountries::<T>::contains_key(&r.destination),
+					Error::<T>::DestinationCountryNotPresent
+				);
+				// check for tokg
+				ensure!(r.tokg > 0, Error::<T>::ToKgIsMissing);
+				// check for rates  (in the currency set)
+				ensure!(r.rate > 0, Error::<T>::ShippingRateCannotbeZero);
 			}
 			// store the shipping rates
+			let oldreferences =
+				ShippingRates::<T>::get(uid).map(|old| old.references()).unwrap_or_default();
+			let oldcount = oldreferences.len() as u32;
+			let newreferences = info.references();
+			let newcount = newreferences.len() as u32;
+			Self::update_references(oldreferences, newreferences);
 			ShippingRates::<T>::insert(uid, info.clone());
 			// Generate event
 			Self::deposit_event(Event::MarketShippingRateCreated(uid, info));
-			// Return a successful DispatchResult
-			Ok(())
+			// Return the weight of the references counted
+			Ok(Some(T::WeightInfo::create_shipping_rates(newcount, oldcount)).into())
 		}
 		/// Destroy Shipping Rates
 		#[pallet::call_index(25)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn destroy_shipping_rates(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+		#[pallet::weight(T::WeightInfo::destroy_shipping_rates(MAX_SHIPPING_RATE_REFERENCES))]
+		pub fn destroy_shipping_rates(
+			origin: OriginFor<T>,
+			uid: u32,
+		) -> DispatchResultWithPostInfo {
+			// check the request is signed from a curator of the shipping
+			Self::ensure_curator(origin, CuratorDomain::Shipping)?;
 			// verify the shipping rate exists
-			ensure!(ShippingRates::<T>::contains_key(uid), Error::<T>::ShippingRatesNotFound);
-			// Remove shipper
-			Shippers::<T>::take(uid);
+			let info = ShippingRates::<T>::get(uid).ok_or(Error::<T>::ShippingRatesNotFound)?;
+			// Remove shipping rates and their references
+			ShippingRates::<T>::take(uid);
+			let references = info.references();
+			let referencescount = references.len() as u32;
+			Self::update_references(references, Vec::new());
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
-			Self::deposit_event(Event::MarketPlaceShipperDestroyed(uid));
-			// Return a successful DispatchResult
-			Ok(())
+			Self::deposit_event(Event::MarketShippingRateDestroyed(uid));
+			// Return the weight of the references removed
+			Ok(Some(T::WeightInfo::destroy_shipping_rates(referencescount)).into())
 		}
 		/// Create a new Brand
-		/// Example of info field: {"name":"Galaxy","manufacturer":7}
+		/// Example: name "Galaxy", manufacturer 7
 		#[pallet::call_index(26)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn create_brand(origin: OriginFor<T>, uid: u32, info: Vec<u8>) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+		pub fn create_brand(origin: OriginFor<T>, uid: u32, info: BrandInfo) -> DispatchResult {
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::BrandUidCannotBeZero);
-			ensure!(info.len() <= 1024, Error::<T>::JsonIsTooLong);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
 			// check for name field
-			let name = json_get_value(info.clone(), "name".as_bytes().to_vec());
-			ensure!(name.len() >= 4, Error::<T>::BrandNameIsTooShort);
-			ensure!(name.len() <= 64, Error::<T>::BrandNameIsTooLong);
-			// check for website field
-			let manufacturer = json_get_value(info.clone(), "manufacturer".as_bytes().to_vec());
-			let mv = vecu8_to_u32(manufacturer);
-			ensure!(mv > 0, Error::<T>::ManufacturerNotFound);
+			ensure!(info.name.len() >= 4, Error::<T>::BrandNameIsTooShort);
 			// check the Manufacturer is  present on chain
-			ensure!(Manufacturers::<T>::contains_key(mv), Error::<T>::ManufacturerNotFound);
+			ensure!(
+				Manufacturers::<T>::contains_key(info.manufacturer),
+				Error::<T>::ManufacturerNotFound
+			);
 			// check the brand is not present on chain
 			ensure!(!Brands::<T>::contains_key(uid), Error::<T>::BrandAlreadyPresent);
 			// store the brand
+			Self::update_references(Vec::new(), info.references());
 			Brands::<T>::insert(uid, info.clone());
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceBrandCreated(uid, info));
@@ -1569,46 +2218,42 @@ pub mod pallet {
 		#[pallet::call_index(27)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_brand(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// verify the brand exists
-			ensure!(Brands::<T>::contains_key(uid), Error::<T>::BrandNotFound);
-			// Remove brand
+			let info = Brands::<T>::get(uid).ok_or(Error::<T>::BrandNotFound)?;
+			// check the brand is not in use
+			Self::ensure_not_in_use(ReferenceKey::Brand(uid))?;
+			// Remove brand and its references
 			Brands::<T>::take(uid);
+			Self::update_references(info.references(), Vec::new());
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceBrandDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
 		}
 		/// Create a new product model
-		/// Example field info: {"name":"A1","brand":1}
+		/// Example: name "A1", brand 1
 		#[pallet::call_index(28)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn create_product_model(
 			origin: OriginFor<T>,
 			uid: u32,
-			info: Vec<u8>,
+			info: ProductModelInfo,
 		) -> DispatchResult {
-			// check the request is signed from root
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// check uid >0
 			ensure!(uid > 0, Error::<T>::ModelUidCannotBeZero);
-			// check valid json
-			ensure!(json_check_validity(info.clone()), Error::<T>::InvalidJson);
 			// check for name field
-			let name = json_get_value(info.clone(), "name".as_bytes().to_vec());
-			ensure!(name.len() >= 2, Error::<T>::ModelNameIsTooShort);
-			ensure!(name.len() <= 32, Error::<T>::ModelNameIsTooLong);
-			// check for brand field
-			let brand = json_get_value(info.clone(), "brand".as_bytes().to_vec());
-			let bv = vecu8_to_u32(brand);
-			ensure!(bv > 0, Error::<T>::BrandNotFound);
+			ensure!(info.name.len() >= 2, Error::<T>::ModelNameIsTooShort);
+			ensure!(info.name.len() <= 32, Error::<T>::ModelNameIsTooLong);
 			// check the brand is  present on chain
-			ensure!(Brands::<T>::contains_key(bv), Error::<T>::BrandNotFound);
+			ensure!(Brands::<T>::contains_key(info.brand), Error::<T>::BrandNotFound);
 			// check the model is not present on chain
 			ensure!(!ProductModels::<T>::contains_key(uid), Error::<T>::ModelAlreadyPresent);
 			// store the model
+			Self::update_references(Vec::new(), info.references());
 			ProductModels::<T>::insert(uid, info.clone());
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceProductModelCreated(uid, info));
@@ -1619,14 +2264,14 @@ pub mod pallet {
 		#[pallet::call_index(29)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn destroy_product_model(origin: OriginFor<T>, uid: u32) -> DispatchResult {
-			// check the request is signed from Super User
-			ensure_root(origin)?;
+			// check the request is signed from a curator of the brands
+			Self::ensure_curator(origin, CuratorDomain::Brands)?;
 			// verify the model exists
-			ensure!(ProductModels::<T>::contains_key(uid), Error::<T>::BrandNotFound);
-			// Remove model
+			let info = ProductModels::<T>::get(uid).ok_or(Error::<T>::BrandNotFound)?;
+			// Remove model and its references
 			ProductModels::<T>::take(uid);
+			Self::update_references(info.references(), Vec::new());
 			// Generate event
-			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceProductModelDestroyed(uid));
 			// Return a successful DispatchResult
 			Ok(())
@@ -1645,16 +2290,24 @@ pub mod pallet {
 			let _sender = ensure_signed(origin)?;
 			// check Email hash length
 			ensure!(emailhash.len() > 8, Error::<T>::WrongLengthEmailHash);
+			let hash: Text =
+				emailhash.clone().try_into().map_err(|_| Error::<T>::WrongLengthEmailHash)?;
 			// check Encrypted Password length
 			ensure!(encryptedpwdhash.len() > 8, Error::<T>::WrongLengthEncryptedPassword);
+			let pwd: EncryptedData = encryptedpwdhash
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::WrongLengthEncryptedPassword)?;
+			let seed: EncryptedData =
+				encryptedseed.try_into().map_err(|_| Error::<T>::EncryptedSeedTooLong)?;
 			// check the email ahsh is not alreay present on chain
-			ensure!(!LoginData::<T>::contains_key(&emailhash), Error::<T>::EmailHashAlreadyPresent);
+			ensure!(!LoginData::<T>::contains_key(&hash), Error::<T>::EmailHashAlreadyPresent);
 			// store the Login Data
-			LoginData::<T>::insert(emailhash.clone(), encryptedpwdhash.clone());
+			LoginData::<T>::insert(&hash, pwd);
 			// store the Account id
-			EmailAccount::<T>::insert(emailhash.clone(), accountid.clone());
+			EmailAccount::<T>::insert(&hash, accountid.clone());
 			// store the encrypted seed (double encryption layer)
-			EmailEncryptedSeed::<T>::insert(emailhash.clone(), encryptedseed.clone());
+			EmailEncryptedSeed::<T>::insert(&hash, seed);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceLoginDataCreated(
 				emailhash,
@@ -1676,19 +2329,23 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 			// check Email hash length
 			ensure!(emailhash.len() > 8, Error::<T>::WrongLengthEmailHash);
+			let hash: Text =
+				emailhash.clone().try_into().map_err(|_| Error::<T>::WrongLengthEmailHash)?;
 			// check Encrypted Password length
 			ensure!(encryptedpwdhash.len() > 8, Error::<T>::WrongLengthEncryptedPassword);
+			let pwd: EncryptedData = encryptedpwdhash
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::WrongLengthEncryptedPassword)?;
 			// check the email hash is alreay present on chain
-			ensure!(LoginData::<T>::contains_key(&emailhash), Error::<T>::EmailHashNotFound);
+			ensure!(LoginData::<T>::contains_key(&hash), Error::<T>::EmailHashNotFound);
 			// check the email account is present on chain
-			ensure!(EmailAccount::<T>::contains_key(&emailhash), Error::<T>::EmailHashNotFound);
-			//let accountidemail=EmailAccount::<T>::get(&emailhash).unw;
-			let accountidemail = EmailAccount::<T>::get(emailhash.clone()).unwrap();
+			let accountidemail =
+				EmailAccount::<T>::get(&hash).ok_or(Error::<T>::EmailHashNotFound)?;
 			// check that the signer is the creator of the original state
 			ensure!(sender == accountidemail, Error::<T>::SignerIsNotAuthorized);
 			// store the Login Data
-			LoginData::<T>::take(&emailhash);
-			LoginData::<T>::insert(emailhash.clone(), encryptedpwdhash.clone());
+			LoginData::<T>::insert(&hash, pwd);
 			// Generate event
 			Self::deposit_event(Event::MarketPlaceLoginPwdChanged(emailhash, encryptedpwdhash));
 			// Return a successful DispatchResult
@@ -1702,283 +2359,2024 @@ pub mod pallet {
 			// check the request is signed from the same signer of the original writing
 			let sender = ensure_signed(origin)?;
 			// verify the login data exists
-			ensure!(LoginData::<T>::contains_key(&emailhash), Error::<T>::EmailHashNotFound);
-			ensure!(EmailAccount::<T>::contains_key(&emailhash), Error::<T>::EmailHashNotFound);
-			let accountid = EmailAccount::<T>::get(emailhash.clone());
+			let hash: Text =
+				emailhash.clone().try_into().map_err(|_| Error::<T>::EmailHashNotFound)?;
+			ensure!(LoginData::<T>::contains_key(&hash), Error::<T>::EmailHashNotFound);
+			ensure!(EmailAccount::<T>::contains_key(&hash), Error::<T>::EmailHashNotFound);
+			let accountid = EmailAccount::<T>::get(&hash);
 			ensure!(
 				accountid == core::prelude::v1::Some(sender),
 				Error::<T>::SignerIsNotAuthorized
 			);
 			// Remove email hash and accountid and encrypted seed
-			LoginData::<T>::take(emailhash.clone());
-			EmailAccount::<T>::take(emailhash.clone());
-			EmailEncryptedSeed::<T>::take(emailhash.clone());
+			LoginData::<T>::take(&hash);
+			EmailAccount::<T>::take(&hash);
+			EmailEncryptedSeed::<T>::take(&hash);
 			// Generate event
 			//it can leave orphans, anyway it's a decision of the super user
 			Self::deposit_event(Event::MarketPlaceLoginDataDestroyed(emailhash));
 			// Return a successful DispatchResult
 			Ok(())
 		}
-	}
-}
-//*************************************************************************************
-//*** functions blocks
-//*************************************************************************************
-// function to validate a json string for no/std. It does not allocate of memory
-fn json_check_validity(j: Vec<u8>) -> bool {
-	// minimum lenght of 2
-	if j.len() < 2 {
-		return false
-	}
-	// checks star/end with {}
-	let first = *j.first().unwrap();
-	let last = *j.last().unwrap();
-	if first == b'{' && last != b'}' {
-		return false
-	}
-	// checks start/end with []
-	if first == b'[' && last != b']' {
-		return false
-	}
-	// check that the start is { or [
-	let first = *j.first().unwrap();
-	if first != b'{' && first != b'[' {
-		return false
-	}
-	//checks that end is } or ]
-	let last = *j.last().unwrap();
-	if last != b'}' && last != b']' {
-		return false
-	}
-	//checks " opening/closing and : as separator between name and values
-	let mut s: bool = true;
-	let mut d: bool = true;
-	let mut pg: bool = true;
-	let mut ps: bool = true;
-	let mut bp = b' ';
-	for b in j {
-		if b == b'[' && s {
-			ps = false;
-		}
-		if b == b']' && s && !ps {
-			ps = true;
-		} else if b == b']' && s && ps {
-			ps = false;
-		}
-		if b == b'{' && s {
-			pg = false;
-		}
-		if b == b'}' && s && !pg {
-			pg = true;
-		} else if b == b'}' && s && pg {
-			pg = false;
-		}
-		if b == b'"' && s && bp != b'\\' {
-			s = false;
-			bp = b;
-			d = false;
-			continue
+		/// Place a new order for a product, the total amount (price * quantity) is reserved from
+		/// the buyer till the delivery is confirmed
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			uid: u32,
+			product: u32,
+			quantity: u32,
+		) -> DispatchResult {
+			// check the request is signed from the buyer
+			let buyer = ensure_signed(origin)?;
+			// check uid >0
+			ensure!(uid > 0, Error::<T>::OrderUidCannotBeZero);
+			// check the order is not already present on chain
+			ensure!(!Orders::<T>::contains_key(uid), Error::<T>::OrderAlreadyPresent);
+			ensure!(quantity > 0, Error::<T>::OrderQuantityCannotBeZero);
+			// check the product and its seller are present on chain
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			let seller = ProductOwner::<T>::get(product).ok_or(Error::<T>::ProductOwnerNotFound)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(buyer != seller, Error::<T>::BuyerCannotBeSeller);
+			// compute the total amount from the product price
+			let price = info.price;
+			ensure!(price > 0, Error::<T>::ProductPriceCannotBeZero);
+			let amount =
+				price.checked_mul(quantity.into()).ok_or(Error::<T>::OrderAmountOverflow)?;
+			// reserve the payment from the buyer
+			T::Currency::reserve(&buyer, amount)?;
+			// store the order
+			let order = Order {
+				buyer: buyer.clone(),
+				seller: seller.clone(),
+				product,
+				quantity,
+				amount,
+				status: OrderStatus::Placed,
+				placedat: <frame_system::Pallet<T>>::block_number(),
+				deliveredat: None,
+			};
+			Orders::<T>::insert(uid, order);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceOrderPlaced(uid, buyer, seller, amount));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if b == b':' && s {
-			d = true;
-			bp = b;
-			continue
+		/// Confirm the shipment of an order, it can be signed only from the seller
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::confirm_shipment())]
+		pub fn confirm_shipment(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			Orders::<T>::try_mutate(uid, |order| -> DispatchResult {
+				let order = order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				// only the seller can confirm the shipment
+				ensure!(order.seller == sender, Error::<T>::SignerIsNotAuthorized);
+				ensure!(order.status == OrderStatus::Placed, Error::<T>::OrderStatusIsWrong);
+				order.status = OrderStatus::Shipped;
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceOrderShipped(uid));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if b == b'"' && !s && bp != b'\\' {
-			s = true;
-			bp = b;
-			d = true;
-			continue
+		/// Confirm the delivery of an order, it can be signed only from the buyer.
+		/// The payment reserved is transferred to the seller, it stays reserved on the seller
+		/// account during the return window of the seller return policy.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::confirm_delivery())]
+		pub fn confirm_delivery(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			let amount = Orders::<T>::try_mutate(uid, |order| -> Result<Balance, DispatchError> {
+				let order = order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				// only the buyer can confirm the delivery
+				ensure!(order.buyer == sender, Error::<T>::SignerIsNotAuthorized);
+				ensure!(order.status == OrderStatus::Shipped, Error::<T>::OrderStatusIsWrong);
+				// release the payment to the seller
+				let returndays = Sellers::<T>::get(&order.seller)
+					.and_then(|seller| seller.defaultreturnpolicy)
+					.unwrap_or(0);
+				let status =
+					if returndays > 0 { BalanceStatus::Reserved } else { BalanceStatus::Free };
+				let missing = T::Currency::repatriate_reserved(
+					&order.buyer,
+					&order.seller,
+					order.amount,
+					status,
+				)?;
+				ensure!(missing == 0, Error::<T>::PaymentTransferFailed);
+				let now = <frame_system::Pallet<T>>::block_number();
+				order.status = OrderStatus::Delivered;
+				order.deliveredat = Some(now);
+				// open the return window
+				if returndays > 0 {
+					let closesat = now
+						.saturating_add(T::BlocksPerDay::get().saturating_mul(returndays.into()));
+					ReturnWindows::<T>::insert(
+						uid,
+						ReturnWindow { closesat, status: ReturnStatus::Open, respondby: None },
+					);
+				}
+				Ok(order.amount)
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceOrderDelivered(uid, amount));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		bp = b;
-	}
-	//fields are not closed properly
-	if !s {
-		return false
-	}
-	//fields are not closed properly
-	if !d {
-		return false
-	}
-	//fields are not closed properly
-	if !ps {
-		return false
-	}
-	// every ok returns true
-	true
-}
-// function to get record {} from multirecord json structure [{..},{.. }], it returns an empty Vec
-// when the records is not present
-fn json_get_recordvalue(ar: Vec<u8>, p: i32) -> Vec<u8> {
-	let mut result = Vec::new();
-	let mut op = true;
-	let mut cn = 0;
-	let mut lb = b' ';
-	for v in ar {
-		if v == b',' && op {
-			cn += 1;
-			continue
+		/// Cancel an order not yet shipped, it can be signed from the buyer or the seller.
+		/// The payment reserved is returned to the buyer.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			Orders::<T>::try_mutate(uid, |order| -> DispatchResult {
+				let order = order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				// only the buyer or the seller can cancel the order
+				ensure!(
+					order.buyer == sender || order.seller == sender,
+					Error::<T>::SignerIsNotAuthorized
+				);
+				ensure!(order.status == OrderStatus::Placed, Error::<T>::OrderStatusIsWrong);
+				// return the payment to the buyer
+				T::Currency::unreserve(&order.buyer, order.amount);
+				order.status = OrderStatus::Cancelled;
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceOrderCancelled(uid));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'[' && op && lb != b'\\' {
-			continue
+		/// Issue a purchase receipt of a product to a buyer, it can be signed only from the seller
+		/// of the product. The receipt allows the buyer to review the product.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::issue_receipt())]
+		pub fn issue_receipt(
+			origin: OriginFor<T>,
+			product: u32,
+			buyer: T::AccountId,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// verify the product exists
+			ensure!(Products::<T>::contains_key(product), Error::<T>::ProductNotFound);
+			// check the signer is the owner of the product
+			ensure!(
+				ProductOwner::<T>::get(product) == Some(sender.clone()),
+				Error::<T>::SignerIsNotAuthorized
+			);
+			ensure!(buyer != sender, Error::<T>::BuyerCannotBeSeller);
+			// store the receipt
+			PurchaseReceipts::<T>::insert(product, &buyer, sender);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceReceiptIssued(product, buyer));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b']' && op && lb != b'\\' {
-			continue
+		/// Review a product with a rating from 1 to 5 and an optional hash of the review text.
+		/// The signer must hold a purchase receipt of the product, a new review replaces the
+		/// previous one of the same signer.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::review_product())]
+		pub fn review_product(
+			origin: OriginFor<T>,
+			product: u32,
+			rating: u8,
+			reviewhash: Option<Vec<u8>>,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// check the rating
+			ensure!((1..=5).contains(&rating), Error::<T>::RatingIsWrong);
+			// check the review hash
+			let reviewhash: Option<ShortText> = reviewhash
+				.map(|hash| ShortText::try_from(hash).map_err(|_| Error::<T>::ReviewHashIsTooLong))
+				.transpose()?;
+			// check the purchase receipt
+			let seller = PurchaseReceipts::<T>::get(product, &sender)
+				.ok_or(Error::<T>::PurchaseReceiptNotFound)?;
+			// remove the previous rating from the aggregates
+			if let Some(oldreview) = Reviews::<T>::get(product, &sender) {
+				ProductRatings::<T>::mutate(product, |r| {
+					r.count = r.count.saturating_sub(1);
+					r.sum = r.sum.saturating_sub(oldreview.rating as u32);
+				});
+				SellerRatings::<T>::mutate(&oldreview.seller, |r| {
+					r.count = r.count.saturating_sub(1);
+					r.sum = r.sum.saturating_sub(oldreview.rating as u32);
+				});
+			}
+			// add the rating to the aggregates
+			let productrating = ProductRatings::<T>::mutate(product, |r| {
+				r.count = r.count.saturating_add(1);
+				r.sum = r.sum.saturating_add(rating as u32);
+				*r
+			});
+			let sellerrating = SellerRatings::<T>::mutate(&seller, |r| {
+				r.count = r.count.saturating_add(1);
+				r.sum = r.sum.saturating_add(rating as u32);
+				*r
+			});
+			// store the review
+			let review = Review {
+				seller: seller.clone(),
+				rating,
+				reviewhash,
+				reviewedat: <frame_system::Pallet<T>>::block_number(),
+			};
+			Reviews::<T>::insert(product, &sender, review);
+			// Generate events
+			Self::deposit_event(Event::MarketPlaceProductReviewed(product, sender, rating));
+			Self::deposit_event(Event::MarketPlaceProductRatingUpdated(
+				product,
+				productrating.count,
+				productrating.sum,
+			));
+			Self::deposit_event(Event::MarketPlaceSellerRatingUpdated(
+				seller,
+				sellerrating.count,
+				sellerrating.sum,
+			));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'{' && op && lb != b'\\' {
-			op = false;
+		/// Set the quantity in stock of a product variant, it can be signed only from the seller
+		/// of the product. The color and size are 0 when the product has no such variants.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::set_stock())]
+		pub fn set_stock(
+			origin: OriginFor<T>,
+			product: u32,
+			color: u32,
+			size: u32,
+			quantity: u32,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// check the signer is the seller and the variant is valid
+			Self::ensure_stock_variant(&sender, product, color, size)?;
+			// store the stock
+			Stock::<T>::insert((product, color, size), quantity);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceStockUpdated(product, color, size, quantity));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'}' && !op && lb != b'\\' {
-			op = true;
+		/// Add (positive delta) or remove (negative delta) items to the stock of a product
+		/// variant, it can be signed only from the seller of the product.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::adjust_stock())]
+		pub fn adjust_stock(
+			origin: OriginFor<T>,
+			product: u32,
+			color: u32,
+			size: u32,
+			delta: i32,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// check the signer is the seller and the variant is valid
+			Self::ensure_stock_variant(&sender, product, color, size)?;
+			// update the stock
+			let quantity = Stock::<T>::try_mutate((product, color, size), |stock| {
+				*stock = if delta >= 0 {
+					stock.checked_add(delta.unsigned_abs()).ok_or(Error::<T>::StorageOverflow)?
+				} else {
+					stock.checked_sub(delta.unsigned_abs()).ok_or(Error::<T>::OutOfStock)?
+				};
+				Ok::<u32, Error<T>>(*stock)
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceStockUpdated(product, color, size, quantity));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		// field found
-		if cn == p {
-			result.push(v);
+		/// Record a sale of a product variant deducting the quantity sold from the stock, it can
+		/// be signed only from the seller of the product.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::record_sale())]
+		pub fn record_sale(
+			origin: OriginFor<T>,
+			product: u32,
+			color: u32,
+			size: u32,
+			quantity: u32,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			ensure!(quantity > 0, Error::<T>::QuantityCannotBeZero);
+			// check the signer is the seller and the variant is valid
+			Self::ensure_stock_variant(&sender, product, color, size)?;
+			// deduct the quantity sold
+			Stock::<T>::try_mutate((product, color, size), |stock| {
+				*stock = stock.checked_sub(quantity).ok_or(Error::<T>::OutOfStock)?;
+				Ok::<(), Error<T>>(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceSaleRecorded(product, color, size, quantity));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		lb = v;
-	}
-	result
-}
-// function to get a field value from array field [1,2,3,4,100], it returns an empty Vec when the
-// records is not present
-fn json_get_arrayvalue(ar: Vec<u8>, p: i32) -> Vec<u8> {
-	let mut result = Vec::new();
-	let mut op = true;
-	let mut cn = 0;
-	let mut lb = b' ';
-	for v in ar {
-		if v == b',' && op {
-			cn += 1;
-			continue
+		/// Open an escrow for a product, the price is reserved from the buyer (signer) till the
+		/// escrow is released or the dispute is resolved
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::open_escrow())]
+		pub fn open_escrow(origin: OriginFor<T>, uid: u32, product: u32) -> DispatchResult {
+			// check the request is signed from the buyer
+			let buyer = ensure_signed(origin)?;
+			// check uid >0
+			ensure!(uid > 0, Error::<T>::EscrowUidCannotBeZero);
+			// check the escrow is not already present on chain
+			ensure!(!Escrows::<T>::contains_key(uid), Error::<T>::EscrowAlreadyPresent);
+			// check the product and its seller are present on chain
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			let seller = ProductOwner::<T>::get(product).ok_or(Error::<T>::ProductOwnerNotFound)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(buyer != seller, Error::<T>::BuyerCannotBeSeller);
+			// get the listed price
+			let amount = info.price;
+			ensure!(amount > 0, Error::<T>::ProductPriceCannotBeZero);
+			// reserve the price from the buyer
+			T::Currency::reserve(&buyer, amount)?;
+			// store the escrow
+			let escrow = Escrow {
+				buyer: buyer.clone(),
+				seller: seller.clone(),
+				product,
+				amount,
+				status: EscrowStatus::Open,
+				openedat: <frame_system::Pallet<T>>::block_number(),
+			};
+			Escrows::<T>::insert(uid, escrow);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceEscrowOpened(uid, buyer, seller, amount));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'[' && op && lb != b'\\' {
-			continue
+		/// Release the escrow to the seller, it can be signed only from the buyer
+		#[pallet::call_index(44)]
+		#[pallet::weight(T::WeightInfo::release_escrow())]
+		pub fn release_escrow(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			let amount =
+				Escrows::<T>::try_mutate(uid, |escrow| -> Result<Balance, DispatchError> {
+					let escrow = escrow.as_mut().ok_or(Error::<T>::EscrowNotFound)?;
+					// only the buyer can release the escrow
+					ensure!(escrow.buyer == sender, Error::<T>::SignerIsNotAuthorized);
+					ensure!(escrow.status == EscrowStatus::Open, Error::<T>::EscrowStatusIsWrong);
+					// transfer the payment to the seller
+					let missing = T::Currency::repatriate_reserved(
+						&escrow.buyer,
+						&escrow.seller,
+						escrow.amount,
+						BalanceStatus::Free,
+					)?;
+					ensure!(missing == 0, Error::<T>::PaymentTransferFailed);
+					escrow.status = EscrowStatus::Released;
+					Ok(escrow.amount)
+				})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceEscrowReleased(uid, amount));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b']' && op && lb != b'\\' {
-			continue
+		/// Open a dispute on an escrow, it can be signed from the buyer or the seller.
+		/// The funds remain reserved till the dispute is resolved from the arbitrators.
+		#[pallet::call_index(45)]
+		#[pallet::weight(T::WeightInfo::open_dispute())]
+		pub fn open_dispute(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			Escrows::<T>::try_mutate(uid, |escrow| -> DispatchResult {
+				let escrow = escrow.as_mut().ok_or(Error::<T>::EscrowNotFound)?;
+				// only the buyer or the seller can open the dispute
+				ensure!(
+					escrow.buyer == sender || escrow.seller == sender,
+					Error::<T>::SignerIsNotAuthorized
+				);
+				ensure!(escrow.status == EscrowStatus::Open, Error::<T>::EscrowStatusIsWrong);
+				escrow.status = EscrowStatus::Disputed;
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceDisputeOpened(uid, sender));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'"' && op && lb != b'\\' {
-			continue
+		/// Submit an evidence for a dispute, it can be signed from the buyer or the seller.
+		/// The evidence is the hash of a document with an optional ipfs address of the document.
+		#[pallet::call_index(46)]
+		#[pallet::weight(T::WeightInfo::submit_evidence())]
+		pub fn submit_evidence(
+			origin: OriginFor<T>,
+			uid: u32,
+			hash: Vec<u8>,
+			ipfs: Option<Vec<u8>>,
+		) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// check the escrow is in dispute
+			let escrow = Escrows::<T>::get(uid).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(
+				escrow.buyer == sender || escrow.seller == sender,
+				Error::<T>::SignerIsNotAuthorized
+			);
+			ensure!(escrow.status == EscrowStatus::Disputed, Error::<T>::EscrowStatusIsWrong);
+			// check the hash
+			ensure!(!hash.is_empty(), Error::<T>::EvidenceHashIsWrong);
+			let evidencehash: ShortText =
+				hash.clone().try_into().map_err(|_| Error::<T>::EvidenceHashIsWrong)?;
+			// check the ipfs address (optional)
+			let ipfs: Option<Text> = ipfs
+				.map(|address| {
+					ensure!(address.len() >= 32, Error::<T>::EvidenceIpfsAddressIsWrong);
+					Text::try_from(address).map_err(|_| Error::<T>::EvidenceIpfsAddressIsWrong)
+				})
+				.transpose()?;
+			// store the evidence
+			DisputeEvidences::<T>::try_mutate(uid, |evidences| -> DispatchResult {
+				evidences
+					.try_push(Evidence {
+						submitter: sender.clone(),
+						hash: evidencehash,
+						ipfs,
+						submittedat: <frame_system::Pallet<T>>::block_number(),
+					})
+					.map_err(|_| Error::<T>::TooManyEvidences)?;
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceEvidenceSubmitted(uid, sender, hash));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'"' && op && lb != b'\\' {
-			op = false;
+		/// Resolve a dispute, it can be submitted only from the arbitrators.
+		/// The escrowed funds are split between the buyer (percentage set) and the seller.
+		#[pallet::call_index(47)]
+		#[pallet::weight(T::WeightInfo::resolve_dispute())]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			uid: u32,
+			buyershare: Percent,
+		) -> DispatchResult {
+			// check the request is signed from the arbitrators
+			T::ArbitratorOrigin::ensure_origin(origin)?;
+			let (buyeramount, selleramount) =
+				Escrows::<T>::try_mutate(uid, |escrow| -> Result<_, DispatchError> {
+					let escrow = escrow.as_mut().ok_or(Error::<T>::EscrowNotFound)?;
+					ensure!(
+						escrow.status == EscrowStatus::Disputed,
+						Error::<T>::EscrowStatusIsWrong
+					);
+					let buyeramount = buyershare * escrow.amount;
+					let selleramount = escrow.amount.saturating_sub(buyeramount);
+					// return the buyer share
+					T::Currency::unreserve(&escrow.buyer, buyeramount);
+					// transfer the seller share
+					let missing = T::Currency::repatriate_reserved(
+						&escrow.buyer,
+						&escrow.seller,
+						selleramount,
+						BalanceStatus::Free,
+					)?;
+					ensure!(missing == 0, Error::<T>::PaymentTransferFailed);
+					escrow.status = EscrowStatus::Resolved;
+					Ok((buyeramount, selleramount))
+				})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceDisputeResolved(uid, buyeramount, selleramount));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if v == b'"' && !op && lb != b'\\' {
-			op = true;
+		/// Add a curator allowed to maintain the reference data of the domain
+		#[pallet::call_index(48)]
+		#[pallet::weight(T::WeightInfo::add_curator())]
+		pub fn add_curator(
+			origin: OriginFor<T>,
+			curator: T::AccountId,
+			domain: CuratorDomain,
+		) -> DispatchResult {
+			// check the request is signed from the curator origin
+			T::CuratorOrigin::ensure_origin(origin)?;
+			// check the curator is not already present
+			ensure!(
+				!Curators::<T>::contains_key(&curator, domain),
+				Error::<T>::CuratorAlreadyPresent
+			);
+			// store the curator
+			Curators::<T>::insert(&curator, domain, ());
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCuratorAdded(curator, domain));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		// field found
-		if cn == p {
-			result.push(v);
+		/// Remove a curator from the domain
+		#[pallet::call_index(49)]
+		#[pallet::weight(T::WeightInfo::remove_curator())]
+		pub fn remove_curator(
+			origin: OriginFor<T>,
+			curator: T::AccountId,
+			domain: CuratorDomain,
+		) -> DispatchResult {
+			// check the request is signed from the curator origin
+			T::CuratorOrigin::ensure_origin(origin)?;
+			// verify the curator exists
+			ensure!(Curators::<T>::contains_key(&curator, domain), Error::<T>::CuratorNotFound);
+			// remove the curator
+			Curators::<T>::remove(&curator, domain);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCuratorRemoved(curator, domain));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		lb = v;
-	}
-	result
-}
-
-// function to get value of a field for Substrate runtime (no std library and no variable
-// allocation)
-fn json_get_value(j: Vec<u8>, key: Vec<u8>) -> Vec<u8> {
-	let mut result = Vec::new();
-	let mut k = Vec::new();
-	let keyl = key.len();
-	let jl = j.len();
-	k.push(b'"');
-	for xk in 0..keyl {
-		k.push(*key.get(xk).unwrap());
-	}
-	k.push(b'"');
-	k.push(b':');
-	let kl = k.len();
-	for x in 0..jl {
-		let mut m = 0;
-		if x + kl > jl {
-			break
+		/// Create an auction of a product of the seller (signer), the auction is settled
+		/// automatically after the duration (number of blocks) or at the first bid of a dutch
+		/// auction
+		#[pallet::call_index(50)]
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			uid: u32,
+			product: u32,
+			auctiontype: AuctionType,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			// check the request is signed from the seller
+			let seller = ensure_signed(origin)?;
+			// check uid >0
+			ensure!(uid > 0, Error::<T>::AuctionUidCannotBeZero);
+			// check the auction is not already present on chain
+			ensure!(!Auctions::<T>::contains_key(uid), Error::<T>::AuctionAlreadyPresent);
+			// check the product belongs to a registered seller
+			ensure!(Products::<T>::contains_key(product), Error::<T>::ProductNotFound);
+			ensure!(
+				ProductOwner::<T>::get(product) == Some(seller.clone()),
+				Error::<T>::SignerIsNotAuthorized
+			);
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			// check the prices of the dutch auction
+			if let AuctionType::Dutch { startprice, decrement, floorprice } = auctiontype {
+				ensure!(
+					startprice > floorprice && decrement > 0,
+					Error::<T>::DutchAuctionPricesAreWrong
+				);
+			}
+			// check the duration
+			ensure!(duration > Zero::zero(), Error::<T>::AuctionDurationCannotBeZero);
+			let startedat = <frame_system::Pallet<T>>::block_number();
+			let endat = startedat.saturating_add(duration);
+			// schedule the settlement
+			AuctionsEndingAt::<T>::try_mutate(endat, |auctions| auctions.try_push(uid))
+				.map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
+			// store the auction
+			let auction = Auction {
+				seller: seller.clone(),
+				product,
+				auctiontype,
+				startedat,
+				endat,
+				highestbid: None,
+			};
+			Auctions::<T>::insert(uid, auction);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceAuctionCreated(uid, seller, product, endat));
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		for (xx, i) in (x..x + kl).enumerate() {
-			if *j.get(i).unwrap() == *k.get(xx).unwrap() {
-				m += 1;
+		/// Bid on an auction, the amount is reserved from the bidder and the outbid bidder is
+		/// refunded. The first bid of a dutch auction not lower than the current price wins, the
+		/// current price is paid.
+		#[pallet::call_index(51)]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(origin: OriginFor<T>, uid: u32, amount: Balance) -> DispatchResult {
+			// check the request is signed from the bidder
+			let bidder = ensure_signed(origin)?;
+			let mut auction = Auctions::<T>::get(uid).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller != bidder, Error::<T>::BuyerCannotBeSeller);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < auction.endat, Error::<T>::AuctionHasEnded);
+			// check the amount of the bid
+			let amount = match auction.auctiontype {
+				AuctionType::English { reserveprice } => {
+					ensure!(amount > 0 && amount >= reserveprice, Error::<T>::BidTooLow);
+					if let Some((_, highestamount)) = &auction.highestbid {
+						ensure!(amount > *highestamount, Error::<T>::BidTooLow);
+					}
+					amount
+				},
+				AuctionType::Dutch { .. } => {
+					let price = Self::auction_price(&auction, now);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+					price
+				},
+			};
+			// reserve the bid and refund the outbid bidder
+			T::Currency::reserve(&bidder, amount)?;
+			if let Some((outbid, outbidamount)) = auction.highestbid.take() {
+				T::Currency::unreserve(&outbid, outbidamount);
 			}
+			auction.highestbid = Some((bidder.clone(), amount));
+			let dutch = matches!(auction.auctiontype, AuctionType::Dutch { .. });
+			Auctions::<T>::insert(uid, auction);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceBidPlaced(uid, bidder, amount));
+			// the first bid wins the dutch auction
+			if dutch {
+				Self::settle_auction(uid);
+			}
+			// Return a successful DispatchResult
+			Ok(())
 		}
-		if m == kl {
-			let mut lb = b' ';
-			let mut op = true;
-			let mut os = true;
-			for i in x + kl..jl - 1 {
-				let v = *j.get(i).unwrap();
-				if v == b'[' && op && os {
-					os = false;
-				}
-				if v == b'}' && op && !os {
-					os = true;
-				}
-				if v == b':' && op {
-					continue
-				}
-				if v == b'"' && op && lb != b'\\' {
-					op = false;
-					continue
+		/// Create a coupon of the seller (signer), the coupon is identified by the hash of its
+		/// code computed with the hashing of the runtime (blake2-256)
+		#[pallet::call_index(52)]
+		#[pallet::weight(T::WeightInfo::create_coupon(coupon.scope.products_count()))]
+		pub fn create_coupon(
+			origin: OriginFor<T>,
+			couponhash: T::Hash,
+			coupon: CouponOf<T>,
+		) -> DispatchResult {
+			// check the request is signed from a seller
+			let seller = ensure_signed(origin)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			// check the coupon is not already present on chain
+			ensure!(
+				!Coupons::<T>::contains_key(&seller, couponhash),
+				Error::<T>::CouponAlreadyPresent
+			);
+			// check the discount
+			let discount = match coupon.discount {
+				Discount::Percentage(percentage) => !percentage.is_zero(),
+				Discount::Fixed(amount) => amount > 0,
+			};
+			ensure!(discount, Error::<T>::CouponDiscountCannotBeZero);
+			// check the validity
+			ensure!(
+				coupon.validto >= coupon.validfrom &&
+					coupon.validto >= <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::CouponValidityIsWrong
+			);
+			// check the products are of the seller
+			match &coupon.scope {
+				CouponScope::AllProducts => {},
+				CouponScope::Products(products) => {
+					ensure!(!products.is_empty(), Error::<T>::CouponProductsCannotBeEmpty);
+					for product in products.iter() {
+						ensure!(
+							ProductOwner::<T>::get(product) == Some(seller.clone()),
+							Error::<T>::ProductNotFound
+						);
+					}
+				},
+				CouponScope::Category(department, category) => {
+					ensure!(
+						ProductCategories::<T>::contains_key(department, category),
+						Error::<T>::ProductCategoryNotFound
+					);
+				},
+			}
+			// store the coupon with a new nonce for its redemptions
+			let nonce = NextCouponNonce::<T>::get();
+			NextCouponNonce::<T>::put(nonce.saturating_add(1));
+			CouponNonces::<T>::insert(&seller, couponhash, nonce);
+			Coupons::<T>::insert(&seller, couponhash, coupon);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCouponCreated(seller, couponhash));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Destroy a coupon of the seller (signer) and its redemptions, a coupon created again
+		/// with the same code starts from zero redemptions
+		#[pallet::call_index(53)]
+		#[pallet::weight(T::WeightInfo::destroy_coupon(MAX_COUPON_BUYERS_REMOVED))]
+		pub fn destroy_coupon(
+			origin: OriginFor<T>,
+			couponhash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			// check the request is signed from the seller
+			let seller = ensure_signed(origin)?;
+			// verify the coupon exists
+			ensure!(Coupons::<T>::contains_key(&seller, couponhash), Error::<T>::CouponNotFound);
+			// Remove the coupon and its redemptions
+			Coupons::<T>::remove(&seller, couponhash);
+			let mut buyers = 0;
+			if let Some(nonce) = CouponNonces::<T>::take(&seller, couponhash) {
+				CouponRedemptions::<T>::remove(nonce);
+				buyers = CouponBuyerRedemptions::<T>::clear_prefix(
+					nonce,
+					MAX_COUPON_BUYERS_REMOVED,
+					None,
+				)
+				.unique;
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCouponDestroyed(seller, couponhash));
+			// Return the weight of the counters of the buyers removed
+			Ok(Some(T::WeightInfo::destroy_coupon(buyers)).into())
+		}
+		/// Redeem a coupon buying a product, the discounted price is transferred from the buyer
+		/// (signer) to the seller
+		#[pallet::call_index(54)]
+		#[pallet::weight(T::WeightInfo::redeem_coupon())]
+		pub fn redeem_coupon(origin: OriginFor<T>, product: u32, code: Vec<u8>) -> DispatchResult {
+			// check the request is signed from the buyer
+			let buyer = ensure_signed(origin)?;
+			// check the product and its seller are present on chain
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			let seller = ProductOwner::<T>::get(product).ok_or(Error::<T>::ProductOwnerNotFound)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(buyer != seller, Error::<T>::BuyerCannotBeSeller);
+			// get the coupon from the hash of the code
+			let couponhash = T::Hashing::hash(&code);
+			let coupon =
+				Coupons::<T>::get(&seller, couponhash).ok_or(Error::<T>::CouponNotFound)?;
+			// check the validity
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= coupon.validfrom && now <= coupon.validto, Error::<T>::CouponIsNotValid);
+			// check the product
+			let valid = match &coupon.scope {
+				CouponScope::AllProducts => true,
+				CouponScope::Products(products) => products.contains(&product),
+				CouponScope::Category(department, category) =>
+					info.category == Some((*department, *category)),
+			};
+			ensure!(valid, Error::<T>::CouponNotValidForProduct);
+			// check the limits of redemptions
+			let nonce =
+				CouponNonces::<T>::get(&seller, couponhash).ok_or(Error::<T>::CouponNotFound)?;
+			let redemptions = CouponRedemptions::<T>::get(nonce);
+			let buyerredemptions = CouponBuyerRedemptions::<T>::get(nonce, &buyer);
+			ensure!(
+				coupon.maxredemptions.map_or(true, |max| redemptions < max) &&
+					coupon.maxredemptionsperbuyer.map_or(true, |max| buyerredemptions < max),
+				Error::<T>::CouponRedemptionsExceeded
+			);
+			// pay the discounted price to the seller, a coupon cannot give the product for free
+			let amount = match coupon.discount {
+				Discount::Percentage(percentage) =>
+					info.price.saturating_sub(percentage * info.price),
+				Discount::Fixed(discount) => info.price.saturating_sub(discount),
+			};
+			ensure!(amount > 0, Error::<T>::DiscountedPriceCannotBeZero);
+			T::Currency::transfer(&buyer, &seller, amount, ExistenceRequirement::KeepAlive)?;
+			// count the redemption and store the receipt of the purchase
+			CouponRedemptions::<T>::insert(nonce, redemptions.saturating_add(1));
+			if coupon.maxredemptionsperbuyer.is_some() {
+				CouponBuyerRedemptions::<T>::insert(
+					nonce,
+					&buyer,
+					buyerredemptions.saturating_add(1),
+				);
+			}
+			PurchaseReceipts::<T>::insert(product, &buyer, seller.clone());
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCouponRedeemed(
+				couponhash, buyer, seller, product, amount,
+			));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Set the billing period in blocks of a product sold as subscription, or remove it to
+		/// stop new subscriptions (the current subscriptions continue)
+		#[pallet::call_index(55)]
+		#[pallet::weight(T::WeightInfo::set_subscription_plan())]
+		pub fn set_subscription_plan(
+			origin: OriginFor<T>,
+			product: u32,
+			period: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			// check the request is signed from the owner of the product
+			let sender = ensure_signed(origin)?;
+			ensure!(Products::<T>::contains_key(product), Error::<T>::ProductNotFound);
+			ensure!(
+				ProductOwner::<T>::get(product) == Some(sender),
+				Error::<T>::SignerIsNotAuthorized
+			);
+			// store the billing period
+			match period {
+				Some(period) => {
+					ensure!(period > Zero::zero(), Error::<T>::SubscriptionPeriodCannotBeZero);
+					SubscriptionPlans::<T>::insert(product, period);
+				},
+				None => SubscriptionPlans::<T>::remove(product),
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceSubscriptionPlanSet(product, period));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Subscribe to a product sold as subscription, the first period is charged immediately
+		/// at the current price of the product
+		#[pallet::call_index(56)]
+		#[pallet::weight(T::WeightInfo::subscribe())]
+		pub fn subscribe(origin: OriginFor<T>, product: u32) -> DispatchResult {
+			// check the request is signed from the subscriber
+			let subscriber = ensure_signed(origin)?;
+			// check the product and its seller are present on chain
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			let period =
+				SubscriptionPlans::<T>::get(product).ok_or(Error::<T>::ProductIsNotSubscription)?;
+			let seller = ProductOwner::<T>::get(product).ok_or(Error::<T>::ProductOwnerNotFound)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(subscriber != seller, Error::<T>::BuyerCannotBeSeller);
+			ensure!(
+				!Subscriptions::<T>::contains_key(product, &subscriber),
+				Error::<T>::AlreadySubscribed
+			);
+			let subscribers = SubscriberCount::<T>::get(product);
+			ensure!(subscribers < MAX_SUBSCRIBERS, Error::<T>::TooManySubscribers);
+			// schedule the next charge
+			let now = <frame_system::Pallet<T>>::block_number();
+			let nextbilling =
+				Self::schedule_billing(product, &subscriber, now.saturating_add(period))
+					.ok_or(Error::<T>::TooManyBillings)?;
+			// charge the first period
+			let amount = info.price;
+			T::Currency::transfer(&subscriber, &seller, amount, ExistenceRequirement::KeepAlive)?;
+			// store the subscription
+			let subscription = Subscription { seller, amount, period, nextbilling };
+			Subscriptions::<T>::insert(product, &subscriber, subscription);
+			SubscriberCount::<T>::insert(product, subscribers + 1);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceSubscribed(product, subscriber, amount));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Unsubscribe from a product, no further periods are charged
+		#[pallet::call_index(57)]
+		#[pallet::weight(T::WeightInfo::unsubscribe())]
+		pub fn unsubscribe(origin: OriginFor<T>, product: u32) -> DispatchResult {
+			// check the request is signed from the subscriber
+			let subscriber = ensure_signed(origin)?;
+			// verify the subscription exists
+			ensure!(
+				Subscriptions::<T>::contains_key(product, &subscriber),
+				Error::<T>::SubscriptionNotFound
+			);
+			// Remove the subscription, the billing scheduled is skipped
+			Subscriptions::<T>::remove(product, &subscriber);
+			SubscriberCount::<T>::mutate(product, |count| *count = count.saturating_sub(1));
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceUnsubscribed(product, subscriber));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Map a currency to the asset used to settle it, or remove the mapping
+		#[pallet::call_index(58)]
+		#[pallet::weight(T::WeightInfo::set_currency_asset())]
+		pub fn set_currency_asset(
+			origin: OriginFor<T>,
+			currencycode: Vec<u8>,
+			assetid: Option<T::AssetId>,
+		) -> DispatchResult {
+			// check the request is signed from a curator of the currencies
+			Self::ensure_curator(origin, CuratorDomain::Currencies)?;
+			// verify the currency code exists
+			let code: CurrencyCode =
+				currencycode.clone().try_into().map_err(|_| Error::<T>::CurrencyCodeNotFound)?;
+			ensure!(Currencies::<T>::contains_key(&code), Error::<T>::CurrencyCodeNotFound);
+			// store the asset
+			match assetid {
+				Some(assetid) => CurrencyAssets::<T>::insert(&code, assetid),
+				None => CurrencyAssets::<T>::remove(&code),
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceCurrencyAssetSet(currencycode, assetid));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Buy a product priced in a currency mapped to an asset, the total amount (price *
+		/// quantity) is transferred in the asset from the buyer to the seller
+		#[pallet::call_index(59)]
+		#[pallet::weight(T::WeightInfo::buy_now())]
+		pub fn buy_now(origin: OriginFor<T>, product: u32, quantity: u32) -> DispatchResult {
+			// check the request is signed from the buyer
+			let buyer = ensure_signed(origin)?;
+			ensure!(quantity > 0, Error::<T>::OrderQuantityCannotBeZero);
+			// check the product and its seller are present on chain
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				Self::product_status(product) == ProductStatus::Active,
+				Error::<T>::ProductIsNotActive
+			);
+			let seller = ProductOwner::<T>::get(product).ok_or(Error::<T>::ProductOwnerNotFound)?;
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			ensure!(buyer != seller, Error::<T>::BuyerCannotBeSeller);
+			// get the asset of the currency of the product
+			let assetid = CurrencyAssets::<T>::get(&info.currency)
+				.ok_or(Error::<T>::CurrencyAssetNotFound)?;
+			// compute the total amount from the product price
+			let price = info.price;
+			ensure!(price > 0, Error::<T>::ProductPriceCannotBeZero);
+			let amount =
+				price.checked_mul(quantity.into()).ok_or(Error::<T>::OrderAmountOverflow)?;
+			// pay the seller in the asset
+			T::Assets::transfer(assetid, &buyer, &seller, amount, Preservation::Preserve)?;
+			// store the receipt of the purchase
+			PurchaseReceipts::<T>::insert(product, &buyer, seller.clone());
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductBought(
+				product, buyer, seller, quantity, amount,
+			));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Set the tax rate of a country, optionally only for a (department, category) of
+		/// products, or remove it
+		#[pallet::call_index(60)]
+		#[pallet::weight(T::WeightInfo::set_tax_rate())]
+		pub fn set_tax_rate(
+			origin: OriginFor<T>,
+			countrycode: Vec<u8>,
+			category: Option<(u32, u32)>,
+			rate: Option<Permill>,
+		) -> DispatchResult {
+			// check the request is signed from the tax origin
+			T::TaxOrigin::ensure_origin(origin)?;
+			// verify the country code and the category exist
+			let code: CountryCode =
+				countrycode.clone().try_into().map_err(|_| Error::<T>::CountryCodeNotFound)?;
+			ensure!(IsoCountries::<T>::contains_key(&code), Error::<T>::CountryCodeNotFound);
+			if let Some((department, category)) = category {
+				ensure!(
+					ProductCategories::<T>::contains_key(department, category),
+					Error::<T>::ProductCategoryNotFound
+				);
+			}
+			let mut references = Vec::from([ReferenceKey::Country(code.clone())]);
+			references.extend(category.map(|(d, c)| ReferenceKey::Category(d, c)));
+			// store the rate and count the references of a new rate
+			let exists = TaxRates::<T>::contains_key(&code, category);
+			match rate {
+				Some(rate) => {
+					if !exists {
+						Self::update_references(Vec::new(), references);
+					}
+					TaxRates::<T>::insert(&code, category, rate);
+				},
+				None => {
+					ensure!(exists, Error::<T>::TaxRateNotFound);
+					TaxRates::<T>::remove(&code, category);
+					Self::update_references(references, Vec::new());
+				},
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceTaxRateSet(countrycode, category, rate));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Issue an invoice to a buyer for the lines (product uid, quantity) of products of the
+		/// seller (signer), the hash of the invoice is anchored as document `uid` of the seller
+		#[pallet::call_index(61)]
+		#[pallet::weight(T::WeightInfo::issue_invoice((lines.len() as u32).min(MAX_INVOICE_LINES)))]
+		pub fn issue_invoice(
+			origin: OriginFor<T>,
+			uid: u32,
+			buyer: T::AccountId,
+			lines: Vec<(u32, u32)>,
+		) -> DispatchResult {
+			// check the request is signed from the seller
+			let seller = ensure_signed(origin)?;
+			// check uid >0
+			ensure!(uid > 0, Error::<T>::InvoiceUidCannotBeZero);
+			// check the invoice is not already present on chain
+			ensure!(!Invoices::<T>::contains_key(uid), Error::<T>::InvoiceAlreadyPresent);
+			ensure!(!lines.is_empty(), Error::<T>::InvoiceLinesCannotBeEmpty);
+			ensure!(lines.len() <= MAX_INVOICE_LINES as usize, Error::<T>::TooManyInvoiceLines);
+			let sellerinfo = Sellers::<T>::get(&seller).ok_or(Error::<T>::SellerDataNotFound)?;
+			ensure!(buyer != seller, Error::<T>::BuyerCannotBeSeller);
+			// build the lines from the products of the seller
+			let mut invoicelines: BoundedVec<InvoiceLine, ConstU32<MAX_INVOICE_LINES>> =
+				BoundedVec::new();
+			let mut currency: Option<CurrencyCode> = None;
+			let mut total: Balance = 0;
+			for (product, quantity) in lines {
+				ensure!(quantity > 0, Error::<T>::OrderQuantityCannotBeZero);
+				let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+				ensure!(
+					ProductOwner::<T>::get(product).as_ref() == Some(&seller),
+					Error::<T>::SignerIsNotAuthorized
+				);
+				// all the lines are in the currency of the first product
+				match &currency {
+					Some(currency) =>
+						ensure!(*currency == info.currency, Error::<T>::InvoiceCurrencyMismatch),
+					None => currency = Some(info.currency.clone()),
 				}
-				if v == b'"' && !op && lb != b'\\' {
-					break
+				let amount = info
+					.price
+					.checked_mul(quantity.into())
+					.ok_or(Error::<T>::OrderAmountOverflow)?;
+				total = total.checked_add(amount).ok_or(Error::<T>::OrderAmountOverflow)?;
+				let line = InvoiceLine {
+					product,
+					description: info.description,
+					quantity,
+					price: info.price,
+					amount,
+				};
+				invoicelines.try_push(line).map_err(|_| Error::<T>::TooManyInvoiceLines)?;
+			}
+			let invoice = Invoice {
+				seller: seller.clone(),
+				sellername: sellerinfo.name,
+				selleraddress: sellerinfo.address,
+				buyer: buyer.clone(),
+				lines: invoicelines,
+				currency: currency.ok_or(Error::<T>::InvoiceLinesCannotBeEmpty)?,
+				total,
+				issuedat: <frame_system::Pallet<T>>::block_number(),
+			};
+			// anchor the hash of the invoice, the parties can co-sign it
+			let invoicehash = T::Hashing::hash_of(&invoice);
+			T::DocumentAnchor::anchor(&seller, uid, invoicehash.as_ref().to_vec())?;
+			// store the invoice
+			Invoices::<T>::insert(uid, invoice);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceInvoiceIssued(uid, seller, buyer, invoicehash));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Set or unset a seller as reseller, the products of the resellers can have the GTIN of
+		/// products of other sellers
+		#[pallet::call_index(62)]
+		#[pallet::weight(T::WeightInfo::set_reseller())]
+		pub fn set_reseller(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			reseller: bool,
+		) -> DispatchResult {
+			// check the request is signed from the reseller origin
+			T::ResellerOrigin::ensure_origin(origin)?;
+			// store the flag
+			if reseller {
+				Resellers::<T>::insert(&account, ());
+			} else {
+				Resellers::<T>::remove(&account);
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceResellerSet(account, reseller));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Attest or reject a certification of a seller by its index in the seller data, the
+		/// verification is valid till the expiry block (included)
+		#[pallet::call_index(63)]
+		#[pallet::weight(T::WeightInfo::attest_certification())]
+		pub fn attest_certification(
+			origin: OriginFor<T>,
+			seller: T::AccountId,
+			index: u32,
+			status: AttestationStatus,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			// check the request is signed from a verifier
+			let verifier = Self::ensure_verifier(origin)?;
+			// check the certification exists
+			let info = Sellers::<T>::get(&seller).ok_or(Error::<T>::SellerDataNotFound)?;
+			ensure!(
+				(index as usize) < info.certifications.len(),
+				Error::<T>::CertificationNotFound
+			);
+			// check the expiry
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(expiry > now, Error::<T>::ExpiryIsWrong);
+			// store the attestation
+			CertificationAttestations::<T>::insert(
+				&seller,
+				index,
+				Attestation { verifier, status, verifiedat: now, expiry },
+			);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceAttestationSet(seller, index, status, expiry));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Set the KYC level of a seller valid till the expiry block (included), the level 0
+		/// removes it
+		#[pallet::call_index(64)]
+		#[pallet::weight(T::WeightInfo::set_seller_kyc())]
+		pub fn set_seller_kyc(
+			origin: OriginFor<T>,
+			seller: T::AccountId,
+			level: u8,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			// check the request is signed from a verifier
+			let verifier = Self::ensure_verifier(origin)?;
+			// check the seller exists
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			// check the level and the expiry
+			ensure!(level <= MAX_KYC_LEVEL, Error::<T>::KycLevelIsWrong);
+			let now = <frame_system::Pallet<T>>::block_number();
+			if level == 0 {
+				SellerKyc::<T>::remove(&seller);
+			} else {
+				ensure!(expiry > now, Error::<T>::ExpiryIsWrong);
+				SellerKyc::<T>::insert(&seller, Kyc { level, verifier, verifiedat: now, expiry });
+			}
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceSellerKycSet(seller, level, expiry));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Request the return of an order delivered, it can be signed only from the buyer till
+		/// the return window closes. The seller must respond in RETURN_RESPONSE_DAYS.
+		#[pallet::call_index(65)]
+		#[pallet::weight(T::WeightInfo::request_return())]
+		pub fn request_return(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// only the buyer can request the return
+			let order = Orders::<T>::get(uid).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.buyer == sender, Error::<T>::SignerIsNotAuthorized);
+			let now = <frame_system::Pallet<T>>::block_number();
+			let respondby = now
+				.saturating_add(T::BlocksPerDay::get().saturating_mul(RETURN_RESPONSE_DAYS.into()));
+			ReturnWindows::<T>::try_mutate(uid, |window| -> DispatchResult {
+				let window = window.as_mut().ok_or(Error::<T>::ReturnWindowNotFound)?;
+				ensure!(window.status == ReturnStatus::Open, Error::<T>::ReturnStatusIsWrong);
+				ensure!(now <= window.closesat, Error::<T>::ReturnWindowClosed);
+				window.status = ReturnStatus::Requested;
+				window.respondby = Some(respondby);
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceReturnRequested(uid, respondby));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Approve the return requested from the buyer, it can be signed only from the seller.
+		/// The payment reserved on the seller account is refunded to the buyer.
+		#[pallet::call_index(66)]
+		#[pallet::weight(T::WeightInfo::approve_return())]
+		pub fn approve_return(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			let amount = Orders::<T>::try_mutate(uid, |order| -> Result<Balance, DispatchError> {
+				let order = order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				// only the seller can approve the return
+				ensure!(order.seller == sender, Error::<T>::SignerIsNotAuthorized);
+				let window =
+					ReturnWindows::<T>::get(uid).ok_or(Error::<T>::ReturnWindowNotFound)?;
+				ensure!(window.status == ReturnStatus::Requested, Error::<T>::ReturnStatusIsWrong);
+				// refund the payment to the buyer
+				Self::refund_return(uid, order)?;
+				Ok(order.amount)
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceReturnApproved(uid, amount));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Reject the return requested from the buyer, it can be signed only from the seller till
+		/// the response deadline
+		#[pallet::call_index(67)]
+		#[pallet::weight(T::WeightInfo::reject_return())]
+		pub fn reject_return(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// only the seller can reject the return
+			let order = Orders::<T>::get(uid).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.seller == sender, Error::<T>::SignerIsNotAuthorized);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ReturnWindows::<T>::try_mutate(uid, |window| -> DispatchResult {
+				let window = window.as_mut().ok_or(Error::<T>::ReturnWindowNotFound)?;
+				ensure!(window.status == ReturnStatus::Requested, Error::<T>::ReturnStatusIsWrong);
+				ensure!(
+					window.respondby.map_or(true, |respondby| now <= respondby),
+					Error::<T>::ReturnResponseExpired
+				);
+				window.status = ReturnStatus::Rejected;
+				Ok(())
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceReturnRejected(uid));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Release the payment reserved on the seller account after the return window closed or
+		/// the return has been rejected, it can be signed only from the seller. The payment of a
+		/// return requested cannot be released.
+		#[pallet::call_index(68)]
+		#[pallet::weight(T::WeightInfo::release_payment())]
+		pub fn release_payment(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			// only the seller can release the payment
+			let order = Orders::<T>::get(uid).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.seller == sender, Error::<T>::SignerIsNotAuthorized);
+			// check the return window
+			let window = ReturnWindows::<T>::get(uid).ok_or(Error::<T>::ReturnWindowNotFound)?;
+			match window.status {
+				ReturnStatus::Open => ensure!(
+					<frame_system::Pallet<T>>::block_number() > window.closesat,
+					Error::<T>::ReturnWindowIsOpen
+				),
+				ReturnStatus::Requested => return Err(Error::<T>::ReturnStatusIsWrong.into()),
+				ReturnStatus::Rejected => {},
+			}
+			// release the payment
+			T::Currency::unreserve(&order.seller, order.amount);
+			ReturnWindows::<T>::remove(uid);
+			// Generate event
+			Self::deposit_event(Event::MarketPlacePaymentReleased(uid, order.amount));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Publish a product in draft or paused, it can be signed only from the seller
+		#[pallet::call_index(69)]
+		#[pallet::weight(T::WeightInfo::publish_product())]
+		pub fn publish_product(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed from the seller and change the status
+			Self::change_product_status(
+				origin,
+				uid,
+				&[ProductStatus::Draft, ProductStatus::Paused],
+				ProductStatus::Active,
+			)?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductPublished(uid));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Pause an active product, it can be signed only from the seller
+		#[pallet::call_index(70)]
+		#[pallet::weight(T::WeightInfo::pause_product())]
+		pub fn pause_product(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed from the seller and change the status
+			Self::change_product_status(
+				origin,
+				uid,
+				&[ProductStatus::Active],
+				ProductStatus::Paused,
+			)?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductPaused(uid));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Discontinue a product, it cannot be published anymore. It can be signed only from the
+		/// seller.
+		#[pallet::call_index(71)]
+		#[pallet::weight(T::WeightInfo::discontinue_product())]
+		pub fn discontinue_product(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed from the seller and change the status
+			Self::change_product_status(
+				origin,
+				uid,
+				&[ProductStatus::Draft, ProductStatus::Active, ProductStatus::Paused],
+				ProductStatus::Discontinued,
+			)?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductDiscontinued(uid));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Assign the seller of a product stored before the products were bound to their sellers,
+		/// the seller can then update or destroy it. The deposit is reserved at the first update.
+		#[pallet::call_index(73)]
+		#[pallet::weight(T::WeightInfo::assign_product_owner())]
+		pub fn assign_product_owner(
+			origin: OriginFor<T>,
+			uid: u32,
+			seller: T::AccountId,
+		) -> DispatchResult {
+			// check the request is signed from the curator origin
+			T::CuratorOrigin::ensure_origin(origin)?;
+			// verify the product exists without an owner
+			ensure!(Products::<T>::contains_key(uid), Error::<T>::ProductNotFound);
+			ensure!(!ProductOwner::<T>::contains_key(uid), Error::<T>::ProductOwnerAlreadySet);
+			// only a registered seller can own products
+			ensure!(Sellers::<T>::contains_key(&seller), Error::<T>::SellerDataNotFound);
+			// store the owner and index the product
+			ProductOwner::<T>::insert(uid, seller.clone());
+			ProductsBySeller::<T>::insert(&seller, uid, ());
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductOwnerAssigned(uid, seller));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+		/// Claim the refund of a return requested when the seller has not responded till the
+		/// response deadline, it can be signed only from the buyer.
+		/// The payment reserved on the seller account is refunded to the buyer.
+		#[pallet::call_index(74)]
+		#[pallet::weight(T::WeightInfo::claim_return())]
+		pub fn claim_return(origin: OriginFor<T>, uid: u32) -> DispatchResult {
+			// check the request is signed
+			let sender = ensure_signed(origin)?;
+			let amount = Orders::<T>::try_mutate(uid, |order| -> Result<Balance, DispatchError> {
+				let order = order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				// only the buyer can claim the refund
+				ensure!(order.buyer == sender, Error::<T>::SignerIsNotAuthorized);
+				let window =
+					ReturnWindows::<T>::get(uid).ok_or(Error::<T>::ReturnWindowNotFound)?;
+				ensure!(window.status == ReturnStatus::Requested, Error::<T>::ReturnStatusIsWrong);
+				ensure!(
+					window.respondby.map_or(false, |respondby| {
+						<frame_system::Pallet<T>>::block_number() > respondby
+					}),
+					Error::<T>::ReturnResponseIsPending
+				);
+				// refund the payment to the buyer
+				Self::refund_return(uid, order)?;
+				Ok(order.amount)
+			})?;
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceReturnApproved(uid, amount));
+			// Return a successful DispatchResult
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Refund the payment of a return reserved on the seller account to the buyer and close
+		/// the return window, it's shared with approve_return and claim_return
+		fn refund_return(uid: u32, order: &mut OrderOf<T>) -> DispatchResult {
+			let missing = T::Currency::repatriate_reserved(
+				&order.seller,
+				&order.buyer,
+				order.amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(missing == 0, Error::<T>::RefundFailed);
+			order.status = OrderStatus::Returned;
+			ReturnWindows::<T>::remove(uid);
+			Ok(())
+		}
+		/// Create or update a product of the seller, it's the body of create_update_product
+		/// shared with batch_upsert_products. Returns the number of references of the previous
+		/// data of the product.
+		fn do_upsert_product(
+			sender: T::AccountId,
+			uid: u32,
+			info: ProductInfo,
+		) -> Result<u32, DispatchError> {
+			// only a registered seller can publish products
+			ensure!(Sellers::<T>::contains_key(&sender), Error::<T>::SellerDataNotFound);
+			// an existing product can be updated only from the seller who created it
+			if Products::<T>::contains_key(uid) {
+				ensure!(
+					ProductOwner::<T>::get(uid) == Some(sender.clone()),
+					Error::<T>::SignerIsNotAuthorized
+				);
+			}
+			// check for mandatory short description
+			ensure!(info.description.len() >= 10, Error::<T>::ProductDescriptionTooShort);
+			// check for mandatory long description
+			ensure!(info.longdescription.len() >= 64, Error::<T>::ProductLongDescriptionTooShort);
+			// check for price >0
+			ensure!(info.price > 0, Error::<T>::ProductPriceCannotBeZero);
+			// check for mandatory currency code
+			ensure!(
+				Currencies::<T>::contains_key(&info.currency),
+				Error::<T>::CurrencyCodeNotFound
+			);
+			// check for department/category (optional), used to index the product
+			if let Some((department, category)) = info.category {
+				ensure!(
+					ProductCategories::<T>::contains_key(department, category),
+					Error::<T>::ProductCategoryNotFound
+				);
+			}
+			// Media is an array of photos,videos and document being part of the product
+			// documentation
+			ensure!(!info.media.is_empty(), Error::<T>::MediaCannotBeEmpty);
+			for m in info.media.iter() {
+				ensure!(!m.description.is_empty(), Error::<T>::MediaDescriptionIswrong);
+				ensure!(!m.filename.is_empty(), Error::<T>::MediaFileNameIsWrong);
+				ensure!(m.ipfs.len() >= 32, Error::<T>::MediaIpfsAddressIsWrong);
+				if let Some(color) = m.color {
+					ensure!(ProductColors::<T>::contains_key(color), Error::<T>::ColorNotFound);
 				}
-				if v == b'}' && op {
-					break
+			}
+			// check colors if enabled
+			for c in info.colors.iter() {
+				ensure!(ProductColors::<T>::contains_key(c), Error::<T>::ColorNotFound);
+			}
+			// check size if enabled
+			for sz in info.sizes.iter() {
+				ensure!(ProductSizes::<T>::contains_key(sz), Error::<T>::SizeNotFound);
+			}
+			// check dimension (optional)
+			for d in info.dimension.iter() {
+				ensure!(d.length > 0, Error::<T>::DimensionWrongLength);
+				ensure!(d.wide > 0, Error::<T>::DimensionWrongWide);
+				ensure!(d.height > 0, Error::<T>::DimensionWrongHeight);
+				ensure!(d.weight > 0, Error::<T>::DimensionWrongWeight);
+			}
+			// check the UPC/EAN/GTIN code and its check digit, the code can be registered from a
+			// single product unless the products are of resellers
+			let gtin =
+				aisland_normalize_gtin(&info.upc).ok_or(Error::<T>::UniversalProductCodeIsWrong)?;
+			if !Resellers::<T>::contains_key(&sender) {
+				ensure!(
+					GtinOwner::<T>::get(gtin).map_or(true, |other| other == uid),
+					Error::<T>::GtinAlreadyRegistered
+				);
+			}
+			// check for shipping countries (optional)
+			for countrycode in info.shippingcountries.iter() {
+				ensure!(
+					IsoCountries::<T>::contains_key(countrycode),
+					Error::<T>::CountryCodeNotFound
+				);
+			}
+			// check for shipping area, the GPS coordinates are in microdegrees
+			for area in info.shippingarea.iter() {
+				ensure!(
+					geo::valid_latitude(area.centerlatitude),
+					Error::<T>::CenterLatitudeIsWrong
+				);
+				ensure!(
+					geo::valid_longitude(area.centerlongitude),
+					Error::<T>::CenterLongitudeIsWrong
+				);
+				ensure!(
+					geo::valid_latitude(area.borderlatitude),
+					Error::<T>::BorderLatitudeIsWrong
+				);
+				ensure!(
+					geo::valid_longitude(area.borderlongitude),
+					Error::<T>::BorderLongitudeIsWrong
+				);
+			}
+			// check for the shippers (optional field)
+			for shipper in info.shippers.iter() {
+				ensure!(Shippers::<T>::contains_key(shipper), Error::<T>::ShipperNotFound);
+			}
+			// check for the API (optional field)
+			if let Some(apiavailability) = &info.apiavailability {
+				ensure!(
+					aisland_validate_weburl(apiavailability.to_vec()),
+					Error::<T>::InvalidApiUrl
+				);
+			}
+			// check for the language if any
+			if let Some(language) = &info.language {
+				ensure!(
+					aisland_validate_languagecode(language.to_vec()),
+					Error::<T>::LanguageCodeIsWrong
+				);
+			}
+			// reserve the deposit for the data stored, adjusting the one of the previous data
+			let deposit = Self::deposit_for(info.encoded_size());
+			Self::update_deposit(&sender, ProductDeposits::<T>::get(uid), deposit)?;
+			ProductDeposits::<T>::insert(uid, deposit);
+			let mut oldreferences = Vec::new();
+			if let Some(oldinfo) = Products::<T>::take(uid) {
+				Self::remove_product_indexes(uid, &sender, &oldinfo);
+				oldreferences = oldinfo.references();
+			} else {
+				// the new products are published from the seller when ready
+				ProductStatuses::<T>::insert(uid, ProductStatus::Draft);
+			}
+			// count the references to the reference data
+			let oldcount = oldreferences.len() as u32;
+			Self::update_references(oldreferences, info.references());
+			Products::<T>::insert(uid, info.clone());
+			ProductOwner::<T>::insert(uid, sender.clone());
+			Self::insert_product_indexes(uid, &sender, &info);
+			// Generate event
+			Self::deposit_event(Event::MarketPlaceProductUpdated(uid, info));
+			Ok(oldcount)
+		}
+		/// Get the current price of a dutch auction or the reserve price of an english auction
+		pub fn auction_price(auction: &AuctionOf<T>, now: T::BlockNumber) -> Balance {
+			match auction.auctiontype {
+				AuctionType::English { reserveprice } => reserveprice,
+				AuctionType::Dutch { startprice, decrement, floorprice } => {
+					let blocks: u128 = now.saturating_sub(auction.startedat).saturated_into();
+					startprice.saturating_sub(decrement.saturating_mul(blocks)).max(floorprice)
+				},
+			}
+		}
+		// queue the charge of a subscription in the block or in the first following block with
+		// room in the billing queue, None when the queue is full for the maximum delay
+		fn schedule_billing(
+			product: u32,
+			subscriber: &T::AccountId,
+			at: T::BlockNumber,
+		) -> Option<T::BlockNumber> {
+			let mut block = at;
+			for _ in 0..MAX_BILLING_DELAY {
+				let queued = BillingQueue::<T>::try_mutate(block, |queue| {
+					queue.try_push((product, subscriber.clone()))
+				});
+				if queued.is_ok() {
+					return Some(block)
 				}
-				if v == b']' && op {
-					break
+				block = block.saturating_add(One::one());
+			}
+			None
+		}
+		// weight of schedule_billing queueing a charge at the block, every block probed is read
+		fn scheduling_weight(at: T::BlockNumber, scheduled: Option<T::BlockNumber>) -> Weight {
+			match scheduled {
+				Some(block) => T::DbWeight::get().reads_writes(
+					block.saturating_sub(at).saturated_into::<u64>().saturating_add(1),
+					1,
+				),
+				None => T::DbWeight::get().reads(MAX_BILLING_DELAY as u64),
+			}
+		}
+		// weight of the charge of a subscription lapsing with the billing queues full for the
+		// maximum delay: the subscription, the product owner, the seller, the balances of the
+		// subscriber and the seller, the billing queues probed and the count of the subscribers
+		fn charge_weight() -> Weight {
+			T::DbWeight::get().reads_writes(6 + MAX_BILLING_DELAY as u64, 4)
+		}
+		// schedule the next charge of the subscription and charge a period, the subscription
+		// lapses without a charge when the next charge cannot be scheduled or the period cannot
+		// be charged. Returns the weight consumed.
+		fn charge_subscription(
+			product: u32,
+			subscriber: &T::AccountId,
+			now: T::BlockNumber,
+		) -> Weight {
+			let mut subscription = match Subscriptions::<T>::get(product, subscriber) {
+				// skip the billings of the subscriptions removed or rescheduled
+				Some(subscription) if subscription.nextbilling == now => subscription,
+				_ => return T::DbWeight::get().reads(1),
+			};
+			let mut weight = T::DbWeight::get().reads_writes(5, 1);
+			// the product must still belong to the seller of the subscription
+			let owned = ProductOwner::<T>::get(product).as_ref() == Some(&subscription.seller) &&
+				Sellers::<T>::contains_key(&subscription.seller);
+			// queue the next charge before charging, a period is never charged without the next
+			// one scheduled. The charge queued for a subscription lapsing is skipped.
+			let nextbilling = if owned {
+				let at = now.saturating_add(subscription.period);
+				let nextbilling = Self::schedule_billing(product, subscriber, at);
+				weight.saturating_accrue(Self::scheduling_weight(at, nextbilling));
+				nextbilling
+			} else {
+				None
+			};
+			let charged = nextbilling.is_some() &&
+				T::Currency::transfer(
+					subscriber,
+					&subscription.seller,
+					subscription.amount,
+					ExistenceRequirement::KeepAlive,
+				)
+				.is_ok();
+			match nextbilling {
+				Some(nextbilling) if charged => {
+					Self::deposit_event(Event::MarketPlaceSubscriptionCharged(
+						product,
+						subscriber.clone(),
+						subscription.amount,
+					));
+					weight.saturating_accrue(T::DbWeight::get().writes(2));
+					subscription.nextbilling = nextbilling;
+					Subscriptions::<T>::insert(product, subscriber, subscription);
+				},
+				_ => {
+					Subscriptions::<T>::remove(product, subscriber);
+					SubscriberCount::<T>::mutate(product, |count| *count = count.saturating_sub(1));
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+					// the subscriber is not charged when the billing queues are full
+					let event = if owned && nextbilling.is_none() {
+						Event::MarketPlaceSubscriptionNotRescheduled(product, subscriber.clone())
+					} else {
+						Event::MarketPlaceSubscriptionLapsed(product, subscriber.clone())
+					};
+					Self::deposit_event(event);
+				},
+			}
+			weight
+		}
+		// delay the charge of a subscription to the first following block with room in the
+		// billing queue, the subscription is charged in the block when the queue is full for the
+		// maximum delay. Returns the weight consumed.
+		fn defer_charge(product: u32, subscriber: &T::AccountId, now: T::BlockNumber) -> Weight {
+			let mut subscription = match Subscriptions::<T>::get(product, subscriber) {
+				Some(subscription) if subscription.nextbilling == now => subscription,
+				_ => return T::DbWeight::get().reads(1),
+			};
+			let at = now.saturating_add(One::one());
+			match Self::schedule_billing(product, subscriber, at) {
+				Some(nextbilling) => {
+					subscription.nextbilling = nextbilling;
+					Subscriptions::<T>::insert(product, subscriber, subscription);
+					T::DbWeight::get()
+						.reads_writes(1, 1)
+						.saturating_add(Self::scheduling_weight(at, Some(nextbilling)))
+				},
+				None => T::DbWeight::get()
+					.reads(1)
+					.saturating_add(Self::scheduling_weight(at, None))
+					.saturating_add(Self::charge_subscription(product, subscriber, now)),
+			}
+		}
+		// weight of the settlement of an auction with a bid refunded after a failed transfer: the
+		// auction, the seller and the balances of the winner and the seller
+		fn settlement_weight() -> Weight {
+			T::DbWeight::get().reads_writes(5, 4)
+		}
+		// transfer the highest bid to the seller or refund it when the seller is no longer
+		// registered, the auction is removed. Returns the weight consumed.
+		fn settle_auction(uid: u32) -> Weight {
+			let auction = match Auctions::<T>::take(uid) {
+				Some(auction) => auction,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			if let Some((winner, amount)) = auction.highestbid {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if Sellers::<T>::contains_key(&auction.seller) {
+					let missing = T::Currency::repatriate_reserved(
+						&winner,
+						&auction.seller,
+						amount,
+						BalanceStatus::Free,
+					);
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+					if missing == Ok(0) {
+						Self::deposit_event(Event::MarketPlaceAuctionSettled(
+							uid,
+							auction.seller,
+							winner,
+							auction.product,
+							amount,
+						));
+						return weight
+					}
 				}
-				if v == b',' && op && os {
-					break
+				T::Currency::unreserve(&winner, amount);
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			}
+			Self::deposit_event(Event::MarketPlaceAuctionClosed(uid));
+			weight
+		}
+		// delay the settlement of an auction to the first following block with room, the auction
+		// is settled in the block when the following blocks are full for the maximum delay.
+		// Returns the weight consumed.
+		fn defer_settlement(uid: u32, now: T::BlockNumber) -> Weight {
+			let mut block = now;
+			for probe in 1..=MAX_SETTLEMENT_DELAY as u64 {
+				block = block.saturating_add(One::one());
+				if AuctionsEndingAt::<T>::try_mutate(block, |auctions| auctions.try_push(uid))
+					.is_ok()
+				{
+					return T::DbWeight::get().reads_writes(probe, 1)
 				}
-				result.push(v);
-				lb = v;
 			}
-			break
+			T::DbWeight::get()
+				.reads(MAX_SETTLEMENT_DELAY as u64)
+				.saturating_add(Self::settle_auction(uid))
 		}
-	}
-	result
-}
-// function to get value of a field with a complex array like [{....},{.....}] for Substrate runtime
-// (no std library and no variable allocation)
-fn json_get_complexarray(j: Vec<u8>, key: Vec<u8>) -> Vec<u8> {
-	let mut result = Vec::new();
-	let mut k = Vec::new();
-	let keyl = key.len();
-	let jl = j.len();
-	k.push(b'"');
-	for xk in 0..keyl {
-		k.push(*key.get(xk).unwrap());
-	}
-	k.push(b'"');
-	k.push(b':');
-	let kl = k.len();
-	for x in 0..jl {
-		let mut m = 0;
-		if x + kl > jl {
-			break
+		// check the signer is the owner of the product and the color/size are variants of the
+		// product (0 when the product does not declare colors/sizes)
+		fn ensure_stock_variant(
+			sender: &T::AccountId,
+			product: u32,
+			color: u32,
+			size: u32,
+		) -> DispatchResult {
+			let info = Products::<T>::get(product).ok_or(Error::<T>::ProductNotFound)?;
+			ensure!(
+				ProductOwner::<T>::get(product).as_ref() == Some(sender),
+				Error::<T>::SignerIsNotAuthorized
+			);
+			let colors = info.colors;
+			let sizes = info.sizes;
+			ensure!(
+				(colors.is_empty() && color == 0) || colors.contains(&color),
+				Error::<T>::VariantNotFound
+			);
+			ensure!(
+				(sizes.is_empty() && size == 0) || sizes.contains(&size),
+				Error::<T>::VariantNotFound
+			);
+			Ok(())
+		}
+		// change the status of a product of the signer from one of the statuses allowed
+		fn change_product_status(
+			origin: OriginFor<T>,
+			uid: u32,
+			allowed: &[ProductStatus],
+			status: ProductStatus,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Products::<T>::contains_key(uid), Error::<T>::ProductNotFound);
+			ensure!(ProductOwner::<T>::get(uid) == Some(sender), Error::<T>::SignerIsNotAuthorized);
+			ensure!(allowed.contains(&Self::product_status(uid)), Error::<T>::ProductStatusIsWrong);
+			ProductStatuses::<T>::insert(uid, status);
+			Ok(())
+		}
+		/// Get the status of a product, the products without status are active
+		pub fn product_status(uid: u32) -> ProductStatus {
+			ProductStatuses::<T>::get(uid).unwrap_or(ProductStatus::Active)
 		}
-		for (xx, i) in (x..x + kl).enumerate() {
-			if *j.get(i).unwrap() == *k.get(xx).unwrap() {
-				m += 1;
+		// check the origin is a signed verifier, the verifier account is stored in the
+		// verifications
+		fn ensure_verifier(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				Curators::<T>::contains_key(&sender, CuratorDomain::Verification),
+				Error::<T>::SignerIsNotVerifier
+			);
+			Ok(sender)
+		}
+		// check the origin is the curator origin or a curator of the domain
+		fn ensure_curator(origin: OriginFor<T>, domain: CuratorDomain) -> DispatchResult {
+			if T::CuratorOrigin::ensure_origin(origin.clone()).is_ok() {
+				return Ok(())
 			}
+			let sender = ensure_signed(origin)?;
+			ensure!(Curators::<T>::contains_key(&sender, domain), Error::<T>::SignerIsNotCurator);
+			Ok(())
 		}
-		if m == kl {
-			let mut os = true;
-			for i in x + kl..jl - 1 {
-				let v = *j.get(i).unwrap();
-				if v == b'[' && os {
-					os = false;
+		// check the reference data is not used
+		fn ensure_not_in_use(key: ReferenceKey) -> DispatchResult {
+			ensure!(!ReferenceCount::<T>::contains_key(key), Error::<T>::InUse);
+			Ok(())
+		}
+		/// Update the reference counters from the references of the previous data to the new ones,
+		/// every reference is counted once for each record
+		pub(crate) fn update_references(old: Vec<ReferenceKey>, new: Vec<ReferenceKey>) {
+			let old: BTreeSet<ReferenceKey> = old.into_iter().collect();
+			let new: BTreeSet<ReferenceKey> = new.into_iter().collect();
+			for key in old.difference(&new) {
+				let count = ReferenceCount::<T>::get(key).saturating_sub(1);
+				if count == 0 {
+					ReferenceCount::<T>::remove(key);
+				} else {
+					ReferenceCount::<T>::insert(key, count);
 				}
-				result.push(v);
-				if v == b']' && !os {
-					break
+			}
+			for key in new.difference(&old) {
+				ReferenceCount::<T>::mutate(key, |count| *count = count.saturating_add(1));
+			}
+		}
+		/// Get the deposit required to store a seller or product of the encoded size
+		pub fn deposit_for(size: usize) -> Balance {
+			T::ByteDeposit::get()
+				.saturating_mul(size as Balance)
+				.saturating_add(T::ItemDeposit::get())
+		}
+		// reserve or unreserve the difference between the deposit held and the new one
+		fn update_deposit(who: &T::AccountId, held: Balance, deposit: Balance) -> DispatchResult {
+			if deposit > held {
+				T::Currency::reserve(who, deposit - held)?;
+			} else {
+				T::Currency::unreserve(who, held - deposit);
+			}
+			Ok(())
+		}
+		// store the product in the secondary indexes (seller, category, currency and GTIN)
+		fn insert_product_indexes(uid: u32, seller: &T::AccountId, info: &ProductInfo) {
+			ProductsBySeller::<T>::insert(seller, uid, ());
+			if let Some(category) = info.category {
+				ProductsByCategory::<T>::insert(category, uid, ());
+			}
+			ProductsByCurrency::<T>::insert(&info.currency, uid, ());
+			if let Some(gtin) = aisland_normalize_gtin(&info.upc) {
+				if Resellers::<T>::contains_key(seller) {
+					ProductsByGtin::<T>::insert(gtin, uid, ());
+				} else {
+					GtinOwner::<T>::insert(gtin, uid);
 				}
 			}
-			break
+		}
+		// remove the product from the secondary indexes (seller, category, currency and GTIN)
+		fn remove_product_indexes(uid: u32, seller: &T::AccountId, info: &ProductInfo) {
+			ProductsBySeller::<T>::remove(seller, uid);
+			if let Some(category) = info.category {
+				ProductsByCategory::<T>::remove(category, uid);
+			}
+			ProductsByCurrency::<T>::remove(&info.currency, uid);
+			if let Some(gtin) = aisland_normalize_gtin(&info.upc) {
+				if GtinOwner::<T>::get(gtin) == Some(uid) {
+					GtinOwner::<T>::remove(gtin);
+				}
+				ProductsByGtin::<T>::remove(gtin, uid);
+			}
+		}
+		/// Get the active products with the barcode (GTIN-8, UPC-A, EAN-13 or GTIN-14), used from
+		/// the runtime api
+		pub fn query_products_by_gtin(code: Vec<u8>) -> Vec<u32> {
+			match aisland_normalize_gtin(&code) {
+				Some(gtin) => GtinOwner::<T>::get(gtin)
+					.into_iter()
+					.chain(ProductsByGtin::<T>::iter_key_prefix(gtin))
+					.filter(|uid| Self::product_status(*uid) == ProductStatus::Active)
+					.collect(),
+				None => Vec::new(),
+			}
+		}
+		/// Get the KYC level and the certifications verified of a seller, only the verifications
+		/// not expired are considered. Used from the runtime api.
+		pub fn query_seller_verification(account: T::AccountId) -> Option<SellerVerification> {
+			if !Sellers::<T>::contains_key(&account) {
+				return None
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			let kyclevel = match SellerKyc::<T>::get(&account) {
+				Some(kyc) if kyc.expiry >= now => kyc.level,
+				_ => 0,
+			};
+			let mut attested = Vec::new();
+			let mut rejected = Vec::new();
+			for (index, attestation) in CertificationAttestations::<T>::iter_prefix(&account) {
+				if attestation.expiry < now {
+					continue
+				}
+				match attestation.status {
+					AttestationStatus::Attested => attested.push(index),
+					AttestationStatus::Rejected => rejected.push(index),
+				}
+			}
+			attested.sort();
+			rejected.sort();
+			Some(SellerVerification {
+				verified: kyclevel > 0 && rejected.is_empty(),
+				kyclevel,
+				attested,
+				rejected,
+			})
+		}
+		/// Get the status of a product, used from the runtime api
+		pub fn query_product_status(uid: u32) -> Option<ProductStatus> {
+			Products::<T>::contains_key(uid).then(|| Self::product_status(uid))
+		}
+		/// Get the seller data, used from the runtime api
+		pub fn query_seller(account: T::AccountId) -> Option<SellerInfoOf<T>> {
+			Sellers::<T>::get(account)
+		}
+		/// Get a page of active products matching the filter, used from the runtime api.
+		/// The most selective index is iterated and the other filters are checked on the indexes.
+		pub fn query_products(
+			filter: ProductFilter<T::AccountId>,
+			start: u32,
+			limit: u32,
+		) -> Vec<(u32, ProductInfo)> {
+			let uids: Box<dyn Iterator<Item = u32>> = if let Some(seller) = filter.seller.clone() {
+				Box::new(ProductsBySeller::<T>::iter_key_prefix(seller))
+			} else if let Some(category) = filter.category {
+				Box::new(ProductsByCategory::<T>::iter_key_prefix(category))
+			} else if let Some(currency) = filter.currency.clone() {
+				Box::new(ProductsByCurrency::<T>::iter_key_prefix(currency))
+			} else {
+				Box::new(Products::<T>::iter_keys())
+			};
+			uids.filter(|uid| match &filter.seller {
+				Some(seller) => ProductsBySeller::<T>::contains_key(seller, uid),
+				None => true,
+			})
+			.filter(|uid| match filter.category {
+				Some(category) => ProductsByCategory::<T>::contains_key(category, uid),
+				None => true,
+			})
+			.filter(|uid| match &filter.currency {
+				Some(currency) => ProductsByCurrency::<T>::contains_key(currency, uid),
+				None => true,
+			})
+			.filter(|uid| Self::product_status(*uid) == ProductStatus::Active)
+			.skip(start as usize)
+			.take(limit.min(MAX_PRODUCTS_PAGE) as usize)
+			.filter_map(|uid| Products::<T>::get(uid).map(|info| (uid, info)))
+			.collect()
+		}
+		/// Get the shipping rates of a shipper, used from the runtime api
+		pub fn query_shipping_rates(shipper: u32) -> Vec<(u32, ShippingRateTable)> {
+			ShippingRates::<T>::iter()
+				.filter(|(_, info)| info.shipperid == shipper)
+				.collect()
+		}
+		/// Get all the currencies, used from the runtime api
+		pub fn query_currencies() -> Vec<(CurrencyCode, CurrencyInfo)> {
+			Currencies::<T>::iter().collect()
+		}
+		/// Check the seller delivers to the GPS coordinates (microdegrees) from its shipment areas,
+		/// used from the runtime api
+		pub fn seller_ships_to(account: T::AccountId, latitude: i32, longitude: i32) -> bool {
+			if !geo::valid_latitude(latitude) || !geo::valid_longitude(longitude) {
+				return false
+			}
+			Sellers::<T>::get(account).map_or(false, |info| {
+				info.shipmentarea.iter().any(|area| area.contains(latitude, longitude))
+			})
+		}
+		/// Check a product is delivered to the GPS coordinates (microdegrees) from its shipping
+		/// areas, or from the shipment areas of its seller when the product has no areas. Used
+		/// from the runtime api.
+		pub fn product_ships_to(uid: u32, latitude: i32, longitude: i32) -> bool {
+			if !geo::valid_latitude(latitude) || !geo::valid_longitude(longitude) {
+				return false
+			}
+			let info = match Products::<T>::get(uid) {
+				Some(info) => info,
+				None => return false,
+			};
+			if info.shippingarea.is_empty() {
+				return ProductOwner::<T>::get(uid)
+					.map_or(false, |seller| Self::seller_ships_to(seller, latitude, longitude))
+			}
+			info.shippingarea.iter().any(|area| area.contains(latitude, longitude))
+		}
+		/// Get the tax line of a product shipped to the destination country, used from the
+		/// runtime api. The rate of the category of the product takes precedence over the rate
+		/// of the country, without rates the tax is zero.
+		pub fn quote_tax(uid: u32, destination: Vec<u8>) -> Option<TaxQuote> {
+			let info = Products::<T>::get(uid)?;
+			let code: CountryCode = destination.try_into().ok()?;
+			let rate = info
+				.category
+				.and_then(|category| TaxRates::<T>::get(&code, Some(category)))
+				.or_else(|| TaxRates::<T>::get(&code, None::<(u32, u32)>))
+				.unwrap_or_default();
+			let tax = rate * info.price;
+			Some(TaxQuote { price: info.price, rate, tax, total: info.price.saturating_add(tax) })
+		}
+		/// Get the shipping quotes (shipper, currency, amount) to deliver a quantity of a product
+		/// to the destination country, used from the runtime api.
+		/// The weight of the product (grams) is taken from the dimension records and matched
+		/// with the fromkg/tokg brackets of the rates of the shippers listed in the product.
+		/// The cheapest rate is returned for every shipper.
+		pub fn quote_shipping(
+			uid: u32,
+			destination: Vec<u8>,
+			quantity: u32,
+		) -> Vec<(u32, Vec<u8>, Balance)> {
+			let mut quotes: Vec<(u32, Vec<u8>, Balance)> = Vec::new();
+			// check the product exists
+			let info = match Products::<T>::get(uid) {
+				Some(info) if quantity > 0 => info,
+				_ => return quotes,
+			};
+			// total weight of the shipment in grams
+			let weight = product_weight(&info).saturating_mul(quantity as u128);
+			if weight == 0 {
+				return quotes
+			}
+			// check the destination is allowed when the shipping countries are set
+			if !info.shippingcountries.is_empty() &&
+				!info.shippingcountries.iter().any(|c| c.as_slice() == destination.as_slice())
+			{
+				return quotes
+			}
+			// match the rates of the shippers of the product
+			for (_, table) in ShippingRates::<T>::iter() {
+				if !info.shippers.contains(&table.shipperid) {
+					continue
+				}
+				for r in table.rates.iter() {
+					// check the destination and the weight bracket
+					if r.destination.as_slice() != destination.as_slice() {
+						continue
+					}
+					let fromkg = (r.fromkg as u128).saturating_mul(1000);
+					let tokg = (r.tokg as u128).saturating_mul(1000);
+					if weight < fromkg || weight > tokg {
+						continue
+					}
+					// keep the cheapest rate of the shipper
+					match quotes.iter_mut().find(|(s, _, _)| *s == table.shipperid) {
+						Some(quote) =>
+							if r.rate < quote.2 {
+								*quote = (table.shipperid, table.currency.to_vec(), r.rate);
+							},
+						None => quotes.push((table.shipperid, table.currency.to_vec(), r.rate)),
+					}
+				}
+			}
+			quotes
 		}
 	}
-	result
 }
+//*************************************************************************************
+//*** functions blocks
+//*************************************************************************************
 // function to validate and email address, return true/false
 fn aisland_validate_email(email: Vec<u8>) -> bool {
 	let mut flagat = false;
@@ -2030,6 +4428,9 @@ fn aisland_validate_weburl(weburl: Vec<u8>) -> bool {
 	let mut startpoint = 0;
 	let https: Vec<u8> = "https://".into();
 	let http: Vec<u8> = "http://".into();
+	if weburl.len() < 8 {
+		return false
+	}
 	let httpscomp: Vec<u8> = weburl[0..8].into();
 	let httpcomp: Vec<u8> = weburl[0..7].into();
 	if https == httpscomp {
@@ -2107,6 +4508,9 @@ fn aisland_validate_phonenumber(phonenumber: Vec<u8>) -> bool {
 	if phonenumber[0] == b'+' {
 		startpoint = 1;
 	}
+	if phonenumber.len() < startpoint + 3 {
+		return false
+	}
 	// create vec for comparison
 	let pc3: Vec<u8> =
 		vec![phonenumber[startpoint], phonenumber[startpoint + 1], phonenumber[startpoint + 2]];
@@ -2165,17 +4569,30 @@ fn aisland_validate_unitmeasurement(unitmeasurement: Vec<u8>) -> bool {
 	}
 	valid
 }
-// function to convert vec<u8> to u32
-fn vecu8_to_u32(v: Vec<u8>) -> u32 {
-	let vslice = v.as_slice();
-	let vstr = str::from_utf8(vslice).unwrap_or("0");
-	let vvalue: u32 = u32::from_str(vstr).unwrap_or(0);
-	vvalue
+// function to validate a GTIN-8, UPC-A (GTIN-12), EAN-13 or GTIN-14 code with its check digit,
+// the code is returned padded to 14 digits
+fn aisland_normalize_gtin(code: &[u8]) -> Option<Gtin> {
+	if ![8, 12, 13, 14].contains(&code.len()) || !code.iter().all(u8::is_ascii_digit) {
+		return None
+	}
+	let mut gtin: Gtin = [b'0'; 14];
+	gtin[14 - code.len()..].copy_from_slice(code);
+	// the digits are weighted 3 and 1 alternately from the right, excluding the check digit
+	let sum: u32 = gtin[..13]
+		.iter()
+		.rev()
+		.enumerate()
+		.map(|(i, d)| (d - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+		.sum();
+	let checkdigit = (10 - sum % 10) % 10;
+	if (gtin[13] - b'0') as u32 != checkdigit {
+		return None
+	}
+	Some(gtin)
 }
-// function to convert vec<u8> to u128
-fn vecu8_to_u128(v: Vec<u8>) -> u128 {
-	let vslice = v.as_slice();
-	let vstr = str::from_utf8(vslice).unwrap_or("0");
-	let vvalue: u128 = u128::from_str(vstr).unwrap_or(0);
-	vvalue
+// function to get the weight (grams) of a product summing the weight of the dimension records
+fn product_weight(info: &ProductInfo) -> u128 {
+	info.dimension
+		.iter()
+		.fold(0, |weight, d| weight.saturating_add(d.weight as u128))
 }
@@ -0,0 +1,630 @@
+//! Storage migrations of the market place
+
+/// Version 1: the records stored as json are converted to the typed records.
+/// The records that cannot be converted (wrong json, fields out of the bounds or wrong GPS
+/// coordinates) are removed.
+/// The other maps keep the same encoding within the new bounds, a bounded vector is encoded like a
+/// vector. Their entries with a key or a value longer than the bounds (for example the login data,
+/// the encrypted seeds and the names of the reference data) cannot be decoded and are removed.
+pub mod v1 {
+	use crate::{
+		json::{self, JsonValue},
+		*,
+	};
+	use codec::DecodeAll;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_io,
+		storage::{unhashed, StoragePrefixedMap},
+		traits::OnRuntimeUpgrade,
+	};
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut count: u64 = 0;
+			Sellers::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				seller_from_json::<T>(&json::parse(&data).ok()?)
+			});
+			Currencies::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				currency_from_json(&json::parse(&data).ok()?)
+			});
+			ProductSizes::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				sizes_from_json(&json::parse(&data).ok()?)
+			});
+			Products::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				product_from_json(&json::parse(&data).ok()?)
+			});
+			Shippers::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				shipper_from_json(&json::parse(&data).ok()?)
+			});
+			ShippingRates::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				shipping_rates_from_json(&json::parse(&data).ok()?)
+			});
+			Manufacturers::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				let json = json::parse(&data).ok()?;
+				Some(ManufacturerInfo {
+					name: bounded(json.get_bytes("name"))?,
+					website: bounded(json.get_bytes("website"))?,
+				})
+			});
+			Brands::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				let json = json::parse(&data).ok()?;
+				Some(BrandInfo {
+					name: bounded(json.get_bytes("name"))?,
+					manufacturer: number(&json, "manufacturer"),
+				})
+			});
+			ProductModels::<T>::translate::<Vec<u8>, _>(|_, data| {
+				count += 1;
+				let json = json::parse(&data).ok()?;
+				Some(ProductModelInfo {
+					name: bounded(json.get_bytes("name"))?,
+					brand: number(&json, "brand"),
+				})
+			});
+			// the entries out of the bounds of the other maps are removed, the keys are checked
+			// also for the currencies as the translation skips the keys it cannot decode
+			let mut removed: u64 = 0;
+			for (checked, out) in [
+				out_of_bounds::<CountryCode, ShortText>(IsoCountries::<T>::final_prefix()),
+				out_of_bounds::<u32, Text>(ProductDepartments::<T>::final_prefix()),
+				out_of_bounds::<u32, Text>(ProductCategories::<T>::final_prefix()),
+				out_of_bounds::<CurrencyCode, CurrencyInfo>(Currencies::<T>::final_prefix()),
+				out_of_bounds::<u32, ShortText>(ProductColors::<T>::final_prefix()),
+				out_of_bounds::<CountryCode, ShortText>(IsoDialcode::<T>::final_prefix()),
+				out_of_bounds::<Text, EncryptedData>(LoginData::<T>::final_prefix()),
+				out_of_bounds::<Text, T::AccountId>(EmailAccount::<T>::final_prefix()),
+				out_of_bounds::<Text, EncryptedData>(EmailEncryptedSeed::<T>::final_prefix()),
+			] {
+				count += checked;
+				removed += out;
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + 1, count + removed + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 1,
+				"the storage version has not been set"
+			);
+			Ok(())
+		}
+	}
+
+	// remove the entries of the map (the first key hashed with Blake2_128Concat) whose first key
+	// or value cannot be decoded with the new types, returns the entries checked and removed
+	fn out_of_bounds<K: Decode, V: Decode>(prefix: [u8; 32]) -> (u64, u64) {
+		let (mut checked, mut removed) = (0, 0);
+		let mut previous = prefix.to_vec();
+		while let Some(key) =
+			sp_io::storage::next_key(&previous).filter(|key| key.starts_with(&prefix))
+		{
+			checked += 1;
+			let decodable = key
+				.get(prefix.len() + 16..)
+				.map_or(false, |mut first| K::decode(&mut first).is_ok()) &&
+				unhashed::get_raw(&key)
+					.map_or(false, |value| V::decode_all(&mut value.as_slice()).is_ok());
+			if !decodable {
+				unhashed::kill(&key);
+				removed += 1;
+			}
+			previous = key;
+		}
+		(checked, removed)
+	}
+
+	// convert the bytes to a bounded vector, None when they are too long
+	fn bounded<S: Get<u32>>(value: Vec<u8>) -> Option<BoundedVec<u8, S>> {
+		value.try_into().ok()
+	}
+	// convert an optional field, None when it's missing or it's too long
+	fn optional<S: Get<u32>>(json: &JsonValue, key: &str) -> Option<BoundedVec<u8, S>> {
+		Some(json.get_bytes(key)).filter(|v| !v.is_empty()).and_then(bounded)
+	}
+	// convert the items of an array field, None when an item cannot be converted or there are too
+	// many items
+	fn items<I, S: Get<u32>>(
+		json: &JsonValue,
+		key: &str,
+		f: impl Fn(&JsonValue) -> Option<I>,
+	) -> Option<BoundedVec<I, S>> {
+		json.get_array(key).iter().map(f).collect::<Option<Vec<I>>>()?.try_into().ok()
+	}
+	// convert a numeric value, 0 when it's wrong
+	fn value<N: FromStr + Default>(json: &JsonValue) -> N {
+		str::from_utf8(&json.to_bytes())
+			.ok()
+			.and_then(|v| v.parse().ok())
+			.unwrap_or_default()
+	}
+	// convert a numeric field, 0 when it's missing or wrong
+	fn number<N: FromStr + Default>(json: &JsonValue, key: &str) -> N {
+		json.get(key).map(value::<N>).unwrap_or_default()
+	}
+	// convert an account stored in base58, None when it's wrong
+	fn account<AccountId: Decode>(value: Vec<u8>) -> Option<AccountId> {
+		let decoded = bs58::decode(value).into_vec().ok()?;
+		AccountId::decode(&mut decoded.get(1..33)?).ok()
+	}
+	// convert the coordinates of an area to microdegrees, None when they are wrong
+	fn area(json: &JsonValue) -> Option<Area> {
+		Area::from_text(
+			&json.get_bytes("centerlatitude"),
+			&json.get_bytes("centerlongitude"),
+			&json.get_bytes("borderlatitude"),
+			&json.get_bytes("borderlongitude"),
+		)
+	}
+
+	fn seller_from_json<T: Config>(json: &JsonValue) -> Option<SellerInfoOf<T>> {
+		let sellertype = match number::<u32>(json, "sellertype") {
+			1 => SellerType::Company,
+			2 => SellerType::Freelancer,
+			3 => SellerType::Individual,
+			4 => SellerType::GovernmentAgency,
+			5 => SellerType::Ngo,
+			_ => return None,
+		};
+		Some(SellerInfo {
+			sellertype,
+			name: bounded(json.get_bytes("name"))?,
+			address: bounded(json.get_bytes("address"))?,
+			zip: bounded(json.get_bytes("zip"))?,
+			pobox: bounded(json.get_bytes("pobox"))?,
+			city: bounded(json.get_bytes("city"))?,
+			websites: items(json, "websites", |w| bounded(w.get_bytes("weburl")))?,
+			socialurls: items(json, "socialurls", |w| bounded(w.get_bytes("socialurl")))?,
+			certifications: items(json, "certifications", |c| {
+				Some(Certification {
+					description: bounded(c.get_bytes("description"))?,
+					verificationurl: bounded(c.get_bytes("verificationurl"))?,
+				})
+			})?,
+			emailinfo: bounded(json.get_bytes("emailinfo"))?,
+			emailsupport: bounded(json.get_bytes("emailsupport"))?,
+			phones: items(json, "phones", |p| {
+				Some(Phone {
+					description: bounded(p.get_bytes("phonedescription"))?,
+					number: bounded(p.get_bytes("phonenumber"))?,
+				})
+			})?,
+			categories: items(json, "categories", |c| {
+				Some((number(c, "department"), number(c, "category")))
+			})?,
+			countries: items(json, "countries", |c| {
+				Some(CountryRule {
+					country: bounded(c.get_bytes("country"))?,
+					excluded: number::<u32>(c, "inout") == 1,
+				})
+			})?,
+			shipmentarea: items(json, "shipmentarea", area)?,
+			defaultlanguage: optional(json, "defaultlanguage"),
+			defaultunitmeasurement: optional(json, "defaultunitmeasurement"),
+			defaultreturnpolicy: Some(json.get_bytes("defaultreturnpolicy"))
+				.filter(|v| !v.is_empty())
+				.map(|_| number(json, "defaultreturnpolicy")),
+			proxyaccount: Some(json.get_bytes("proxyaccount"))
+				.filter(|v| !v.is_empty())
+				.and_then(account),
+		})
+	}
+
+	fn currency_from_json(json: &JsonValue) -> Option<CurrencyInfo> {
+		let category = match json.get_bytes("category").as_slice() {
+			b"c" => CurrencyCategory::Crypto,
+			b"f" => CurrencyCategory::Fiat,
+			_ => return None,
+		};
+		Some(CurrencyInfo {
+			name: bounded(json.get_bytes("name"))?,
+			category,
+			country: optional(json, "country"),
+			blockchain: optional(json, "blockchain"),
+			address: optional(json, "address"),
+		})
+	}
+
+	fn sizes_from_json(json: &JsonValue) -> Option<SizeTable> {
+		let sizes = match json {
+			JsonValue::Array(items) => items.as_slice(),
+			_ => core::slice::from_ref(json),
+		};
+		sizes
+			.iter()
+			.map(|sz| {
+				Some(SizeInfo {
+					code: bounded(sz.get_bytes("code"))?,
+					description: bounded(sz.get_bytes("description"))?,
+					area: bounded(sz.get_bytes("area"))?,
+				})
+			})
+			.collect::<Option<Vec<SizeInfo>>>()?
+			.try_into()
+			.ok()
+	}
+
+	fn product_from_json(json: &JsonValue) -> Option<ProductInfo> {
+		let department: u32 = number(json, "department");
+		let category: u32 = number(json, "category");
+		Some(ProductInfo {
+			description: bounded(json.get_bytes("description"))?,
+			longdescription: bounded(json.get_bytes("longdescription"))?,
+			price: number(json, "price"),
+			currency: bounded(json.get_bytes("currency"))?,
+			category: Some((department, category)).filter(|(d, c)| *d > 0 && *c > 0),
+			specifications: bounded(json.get_bytes("specifications"))?,
+			media: items(json, "media", |m| {
+				Some(Media {
+					description: bounded(m.get_bytes("description"))?,
+					filename: bounded(m.get_bytes("filename"))?,
+					ipfs: bounded(m.get_bytes("ipfs"))?,
+					color: Some(number::<u32>(m, "color")).filter(|c| *c > 0),
+				})
+			})?,
+			colors: items(json, "colors", |c| Some(value(c)))?,
+			sizes: items(json, "sizes", |s| Some(value(s)))?,
+			dimension: items(json, "dimension", |d| {
+				Some(Dimension {
+					length: number(d, "length"),
+					wide: number(d, "wide"),
+					height: number(d, "height"),
+					weight: number(d, "weight"),
+				})
+			})?,
+			upc: bounded(json.get_bytes("upc"))?,
+			shippingcountries: items(json, "shippingcountries", |c| bounded(c.to_bytes()))?,
+			shippingarea: items(json, "shippingarea", area)?,
+			shippers: items(json, "shippers", |s| Some(value(s)))?,
+			apiavailability: optional(json, "apiavailability"),
+			language: optional(json, "language"),
+		})
+	}
+
+	fn shipper_from_json(json: &JsonValue) -> Option<ShipperInfo> {
+		Some(ShipperInfo {
+			name: bounded(json.get_bytes("name"))?,
+			website: bounded(json.get_bytes("website"))?,
+			origincountries: items(json, "origincountries", |c| bounded(c.to_bytes()))?,
+			destinationcountries: items(json, "destinationcountries", |c| bounded(c.to_bytes()))?,
+		})
+	}
+
+	fn shipping_rates_from_json(json: &JsonValue) -> Option<ShippingRateTable> {
+		Some(ShippingRateTable {
+			shipperid: number(json, "shipperid"),
+			origincountry: bounded(json.get_bytes("origincountry"))?,
+			currency: bounded(json.get_bytes("currency"))?,
+			rates: items(json, "rates", |r| {
+				Some(ShippingRate {
+					destination: bounded(r.get_bytes("destination"))?,
+					fromkg: number(r, "fromkg"),
+					tokg: number(r, "tokg"),
+					rate: number(r, "rate"),
+				})
+			})?,
+		})
+	}
+}
+
+/// Version 2: the references from the stored records to the reference data are counted.
+pub mod v2 {
+	use crate::*;
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 2 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut references = Vec::new();
+			references.extend(Sellers::<T>::iter_values().map(|info| info.references()));
+			references.extend(Products::<T>::iter_values().map(|info| info.references()));
+			references.extend(Currencies::<T>::iter_values().map(|info| info.references()));
+			references.extend(Shippers::<T>::iter_values().map(|info| info.references()));
+			references.extend(ShippingRates::<T>::iter_values().map(|info| info.references()));
+			references.extend(Brands::<T>::iter_values().map(|info| info.references()));
+			references.extend(ProductModels::<T>::iter_values().map(|info| info.references()));
+			let count = references.len() as u64;
+			let mut writes: u64 = 0;
+			for keys in references {
+				writes += keys.len() as u64;
+				Pallet::<T>::update_references(Vec::new(), keys);
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + writes + 1, writes + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 2,
+				"the storage version has not been set"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 3: the GPS coordinates of the shipping areas of the sellers and the products are
+/// stored in microdegrees in place of the text. The areas with coordinates that cannot be parsed
+/// or out of range are removed. The records already stored with the new encoding (converted from
+/// json by the version 1 in the same upgrade) are kept.
+pub mod v3 {
+	use crate::*;
+	use codec::DecodeAll;
+	use frame_support::{pallet_prelude::*, storage::unhashed, traits::OnRuntimeUpgrade};
+
+	/// Records of the version 2 with the coordinates stored as text
+	pub mod old {
+		use crate::*;
+		use frame_support::pallet_prelude::*;
+
+		#[derive(Encode, Decode)]
+		pub struct Area {
+			pub centerlatitude: ShortText,
+			pub centerlongitude: ShortText,
+			pub borderlatitude: ShortText,
+			pub borderlongitude: ShortText,
+		}
+
+		#[derive(Encode, Decode)]
+		pub struct SellerInfo<AccountId> {
+			pub sellertype: SellerType,
+			pub name: ShortText,
+			pub address: Text,
+			pub zip: ShortText,
+			pub pobox: ShortText,
+			pub city: ShortText,
+			pub websites: BoundedVec<Text, ConstU32<8>>,
+			pub socialurls: BoundedVec<Text, ConstU32<8>>,
+			pub certifications: BoundedVec<Certification, ConstU32<16>>,
+			pub emailinfo: ShortText,
+			pub emailsupport: ShortText,
+			pub phones: BoundedVec<Phone, ConstU32<8>>,
+			pub categories: BoundedVec<(u32, u32), ConstU32<32>>,
+			pub countries: BoundedVec<CountryRule, ConstU32<256>>,
+			pub shipmentarea: BoundedVec<Area, ConstU32<8>>,
+			pub defaultlanguage: Option<LanguageCode>,
+			pub defaultunitmeasurement: Option<UnitMeasurement>,
+			pub defaultreturnpolicy: Option<u32>,
+			pub proxyaccount: Option<AccountId>,
+		}
+
+		#[derive(Encode, Decode)]
+		pub struct ProductInfo {
+			pub description: ShortText,
+			pub longdescription: LongText,
+			pub price: Balance,
+			pub currency: CurrencyCode,
+			pub category: Option<(u32, u32)>,
+			pub specifications: LongText,
+			pub media: BoundedVec<Media, ConstU32<16>>,
+			pub colors: BoundedVec<u32, ConstU32<32>>,
+			pub sizes: BoundedVec<u32, ConstU32<32>>,
+			pub dimension: BoundedVec<Dimension, ConstU32<8>>,
+			pub upc: ShortText,
+			pub shippingcountries: BoundedVec<CountryCode, ConstU32<256>>,
+			pub shippingarea: BoundedVec<Area, ConstU32<8>>,
+			pub shippers: BoundedVec<u32, ConstU32<16>>,
+			pub apiavailability: Option<Text>,
+			pub language: Option<LanguageCode>,
+		}
+	}
+
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 3 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut count: u64 = 0;
+			let mut writes: u64 = 0;
+			for account in Sellers::<T>::iter_keys().collect::<Vec<_>>() {
+				count += 1;
+				let old = match raw::<SellerInfoOf<T>, old::SellerInfo<T::AccountId>>(
+					Sellers::<T>::hashed_key_for(&account),
+				) {
+					Some(old) => old,
+					None => continue,
+				};
+				let info = SellerInfo {
+					sellertype: old.sellertype,
+					name: old.name,
+					address: old.address,
+					zip: old.zip,
+					pobox: old.pobox,
+					city: old.city,
+					websites: old.websites,
+					socialurls: old.socialurls,
+					certifications: old.certifications,
+					emailinfo: old.emailinfo,
+					emailsupport: old.emailsupport,
+					phones: old.phones,
+					categories: old.categories,
+					countries: old.countries,
+					shipmentarea: areas(old.shipmentarea),
+					defaultlanguage: old.defaultlanguage,
+					defaultunitmeasurement: old.defaultunitmeasurement,
+					defaultreturnpolicy: old.defaultreturnpolicy,
+					proxyaccount: old.proxyaccount,
+				};
+				Sellers::<T>::insert(&account, info);
+				writes += 1;
+			}
+			for uid in Products::<T>::iter_keys().collect::<Vec<_>>() {
+				count += 1;
+				let old = match raw::<ProductInfo, old::ProductInfo>(Products::<T>::hashed_key_for(
+					uid,
+				)) {
+					Some(old) => old,
+					None => continue,
+				};
+				let info = ProductInfo {
+					description: old.description,
+					longdescription: old.longdescription,
+					price: old.price,
+					currency: old.currency,
+					category: old.category,
+					specifications: old.specifications,
+					media: old.media,
+					colors: old.colors,
+					sizes: old.sizes,
+					dimension: old.dimension,
+					upc: old.upc,
+					shippingcountries: old.shippingcountries,
+					shippingarea: areas(old.shippingarea),
+					shippers: old.shippers,
+					apiavailability: old.apiavailability,
+					language: old.language,
+				};
+				Products::<T>::insert(uid, info);
+				writes += 1;
+			}
+			StorageVersion::new(3).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + 1, writes + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 3,
+				"the storage version has not been set"
+			);
+			Ok(())
+		}
+	}
+
+	// decode a record with the old encoding, None when it's already in the new encoding or it
+	// cannot be decoded
+	fn raw<New: Decode, Old: Decode>(key: Vec<u8>) -> Option<Old> {
+		let data = unhashed::get_raw(&key)?;
+		if New::decode_all(&mut data.as_slice()).is_ok() {
+			return None
+		}
+		Old::decode_all(&mut data.as_slice()).ok()
+	}
+
+	// convert the areas, the areas with wrong coordinates are removed
+	fn areas(old: BoundedVec<old::Area, ConstU32<8>>) -> BoundedVec<Area, ConstU32<8>> {
+		BoundedVec::truncate_from(
+			old.iter()
+				.filter_map(|a| {
+					Area::from_text(
+						&a.centerlatitude,
+						&a.centerlongitude,
+						&a.borderlatitude,
+						&a.borderlongitude,
+					)
+				})
+				.collect(),
+		)
+	}
+}
+
+/// Version 4: the products are indexed by GTIN, the products with an upc field that is not a
+/// valid GTIN are not indexed. The first product of a GTIN holds the code, the products of the
+/// resellers and the other products with the same code are stored in the index of the resellers.
+pub mod v4 {
+	use crate::*;
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 4 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut count: u64 = 0;
+			let mut writes: u64 = 0;
+			for (uid, info) in Products::<T>::iter() {
+				count += 1;
+				if let Some(gtin) = aisland_normalize_gtin(&info.upc) {
+					let reseller = ProductOwner::<T>::get(uid)
+						.map_or(false, |owner| Resellers::<T>::contains_key(owner));
+					if reseller || GtinOwner::<T>::contains_key(gtin) {
+						ProductsByGtin::<T>::insert(gtin, uid, ());
+					} else {
+						GtinOwner::<T>::insert(gtin, uid);
+					}
+					count += 3;
+					writes += 1;
+				}
+			}
+			StorageVersion::new(4).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + 1, writes + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 4,
+				"the storage version has not been set"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Version 5: the products stored before the secondary indexes are indexed by category and
+/// currency, and by seller when their owner is known. The products without owner are indexed by
+/// seller when their seller is assigned.
+pub mod v5 {
+	use crate::*;
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 5 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut count: u64 = 0;
+			let mut writes: u64 = 0;
+			for (uid, info) in Products::<T>::iter() {
+				count += 2;
+				if let Some(seller) = ProductOwner::<T>::get(uid) {
+					ProductsBySeller::<T>::insert(seller, uid, ());
+					writes += 1;
+				}
+				if let Some(category) = info.category {
+					ProductsByCategory::<T>::insert(category, uid, ());
+					writes += 1;
+				}
+				ProductsByCurrency::<T>::insert(&info.currency, uid, ());
+				writes += 1;
+			}
+			StorageVersion::new(5).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(count + 1, writes + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() >= 5,
+				"the storage version has not been set"
+			);
+			Ok(())
+		}
+	}
+}
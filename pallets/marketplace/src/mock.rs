@@ -1,5 +1,10 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_marketplace;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU16, ConstU32, ConstU64},
+	weights::{constants::RocksDbWeight, Weight},
+};
+use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -17,7 +22,10 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		DocSig: pallet_docsig,
+		MarketPlace: pallet_marketplace,
 	}
 );
 
@@ -25,7 +33,7 @@ impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Index = u64;
@@ -39,7 +47,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,12 +56,106 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin =
+		frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl pallet_marketplace::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ArbitratorOrigin = frame_system::EnsureRoot<u64>;
+	type CuratorOrigin = frame_system::EnsureRoot<u64>;
+	type ItemDeposit = ConstU128<100>;
+	type ByteDeposit = ConstU128<1>;
+	type AssetId = u32;
+	type Assets = Assets;
+	type TaxOrigin = frame_system::EnsureRoot<u64>;
+	type DocumentAnchor = DocSigAnchor;
+	type ResellerOrigin = frame_system::EnsureRoot<u64>;
+	type BlocksPerDay = ConstU64<10>;
+	type MaxProductsBatch = ConstU32<4>;
+	type MaxInitializeWeight = MaxInitializeWeight;
+}
+
+parameter_types! {
+	// lowered from the tests of the weight limit of the hooks
+	pub static MaxInitializeWeight: Weight = Weight::MAX;
+}
+
+impl pallet_docsig::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 }
 
-// Build genesis storage according to the mock runtime.
+// anchor the invoices in pallet-docsig as the runtime does
+pub struct DocSigAnchor;
+impl pallet_marketplace::DocumentAnchor<u64> for DocSigAnchor {
+	fn anchor(owner: &u64, id: u32, hash: Vec<u8>) -> sp_runtime::DispatchResult {
+		DocSig::do_new_document(*owner, id, hash)
+	}
+}
+
+// Build genesis storage according to the mock runtime starting from block(1)
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	// accounts 1,2,3 are funded
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1_000_000), (2, 1_000_000), (3, 1_000_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	// need to set block number to 1 to test events
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn last_event() -> RuntimeEvent {
+	System::events().pop().expect("Event expected").event
+}
+
+// move to the block executing the hooks of the market place
+pub fn run_to_block(n: u64) {
+	use frame_support::traits::Hooks;
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		MarketPlace::on_initialize(System::block_number());
+	}
 }
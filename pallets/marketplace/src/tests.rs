@@ -1,27 +1,2181 @@
-// use crate::{mock::*, Error, Event};
-// use frame_support::{assert_noop, assert_ok};
-
-// #[test]
-// fn it_works_for_default_value() {
-// 	new_test_ext().execute_with(|| {
-// 		// Go past genesis block so events get deposited
-// 		System::set_block_number(1);
-// 		// Dispatch a signed extrinsic.
-// 		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-// 		// Read pallet storage and assert an expected result.
-// 		assert_eq!(TemplateModule::something(), Some(42));
-// 		// Assert that the correct event was deposited
-// 		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
-// 	});
-// }
-
-// #[test]
-// fn correct_error_for_none_value() {
-// 	new_test_ext().execute_with(|| {
-// 		// Ensure the expected error is thrown when no value is present.
-// 		assert_noop!(
-// 			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-// 			Error::<Test>::NoneValue
-// 		);
-// 	});
-// }
+use crate::{
+	aisland_normalize_gtin, geo,
+	json::{self, JsonError},
+	migrations,
+	mock::*,
+	Area, AttestationStatus, AuctionType, Auctions, AuctionsEndingAt, BillingQueue, Certification,
+	CertificationAttestations, CountryRule, Coupon, CouponBuyerRedemptions, CouponNonces,
+	CouponRedemptions, CouponScope, CuratorDomain, Currencies, CurrencyAssets, CurrencyCategory,
+	CurrencyCode, CurrencyInfo, Dimension, Discount, DisputeEvidences, EmailEncryptedSeed, Error,
+	EscrowStatus, Escrows, Event, GtinOwner, Invoices, IsoCountries, LoginData, Media, OrderStatus,
+	Orders, ProductCategories, ProductDeposits, ProductFilter, ProductInfo, ProductOwner,
+	ProductRatings, ProductStatus, Products, ProductsByCategory, ProductsByCurrency,
+	ProductsByGtin, ProductsBySeller, PurchaseReceipts, RatingSummary, ReferenceCount,
+	ReferenceKey, ReturnStatus, ReturnWindow, ReturnWindows, Reviews, SellerDeposits, SellerInfo,
+	SellerKyc, SellerRatings, SellerType, SellerVerification, Sellers, ShipperInfo, Shippers,
+	ShippingRate, ShippingRateTable, ShippingRates, Stock, SubscriberCount, Subscription,
+	SubscriptionPlans, Subscriptions, TaxQuote, WeightInfo, MAX_BILLINGS_PER_BLOCK,
+	MAX_BILLING_DELAY, MAX_PRODUCT_REFERENCES, MAX_SUBSCRIBERS,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{Get, StorageVersion},
+	storage::{unhashed, StoragePrefixedMap},
+	traits::OnRuntimeUpgrade,
+	weights::constants::RocksDbWeight,
+	Blake2_128Concat, BoundedVec, StorageHasher,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, Percent, Permill,
+};
+
+// convert the bytes to a bounded vector
+fn bounded<S: Get<u32>>(value: &[u8]) -> BoundedVec<u8, S> {
+	value.to_vec().try_into().unwrap()
+}
+
+// build a valid seller
+fn seller_info(name: &[u8]) -> SellerInfo<u64> {
+	SellerInfo {
+		sellertype: SellerType::Company,
+		name: bounded(name),
+		address: bounded(b"Broad Street 1"),
+		zip: bounded(b"1000"),
+		pobox: BoundedVec::default(),
+		city: bounded(b"Monrovia"),
+		websites: BoundedVec::default(),
+		socialurls: BoundedVec::default(),
+		certifications: BoundedVec::default(),
+		emailinfo: bounded(b"info@seller.com"),
+		emailsupport: bounded(b"support@seller.com"),
+		phones: BoundedVec::default(),
+		categories: vec![(1, 1)].try_into().unwrap(),
+		countries: vec![CountryRule { country: bounded(b"LR"), excluded: false }]
+			.try_into()
+			.unwrap(),
+		shipmentarea: BoundedVec::default(),
+		defaultlanguage: None,
+		defaultunitmeasurement: None,
+		defaultreturnpolicy: Some(30),
+		proxyaccount: None,
+	}
+}
+
+// build a valid product
+fn product_info(currency: &[u8], category: Option<(u32, u32)>, price: u128) -> ProductInfo {
+	ProductInfo {
+		description: bounded(b"Product description"),
+		longdescription: bounded(
+			b"Long description of the product with all the details required from buyers",
+		),
+		price,
+		currency: bounded(currency),
+		category,
+		specifications: BoundedVec::default(),
+		media: vec![Media {
+			description: bounded(b"front"),
+			filename: bounded(b"front.jpg"),
+			ipfs: bounded(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+			color: None,
+		}]
+		.try_into()
+		.unwrap(),
+		colors: BoundedVec::default(),
+		sizes: BoundedVec::default(),
+		dimension: BoundedVec::default(),
+		upc: bounded(b"012345678905"),
+		shippingcountries: BoundedVec::default(),
+		shippingarea: BoundedVec::default(),
+		shippers: BoundedVec::default(),
+		apiavailability: None,
+		language: None,
+	}
+}
+
+// build a crypto currency
+fn currency_info(name: &[u8]) -> CurrencyInfo {
+	CurrencyInfo {
+		name: bounded(name),
+		category: CurrencyCategory::Crypto,
+		country: None,
+		blockchain: Some(bounded(b"Aisland")),
+		address: None,
+	}
+}
+
+// store a seller and a product with the minimum data required from the orders
+fn setup_product(seller: u64, product: u32, price: u128) {
+	Sellers::<Test>::insert(seller, seller_info(b"Seller Ltd"));
+	Products::<Test>::insert(product, product_info(b"USDC", None, price));
+	ProductOwner::<Test>::insert(product, seller);
+}
+
+// test the order lifecycle placed -> shipped -> delivered
+#[test]
+fn test_order_delivered() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		// place an order for 3 items, the amount is reserved from the buyer
+		assert_ok!(MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 3));
+		assert_eq!(last_event(), Event::MarketPlaceOrderPlaced(1, 1, 2, 300).into());
+		assert_eq!(Balances::reserved_balance(1), 300);
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 300);
+		// the same order uid cannot be placed twice
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 3),
+			Error::<Test>::OrderAlreadyPresent
+		);
+		// the buyer cannot confirm the delivery before the shipment
+		assert_noop!(
+			MarketPlace::confirm_delivery(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::OrderStatusIsWrong
+		);
+		// only the seller can confirm the shipment
+		assert_noop!(
+			MarketPlace::confirm_shipment(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::confirm_shipment(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceOrderShipped(1).into());
+		// a shipped order cannot be cancelled
+		assert_noop!(
+			MarketPlace::cancel_order(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::OrderStatusIsWrong
+		);
+		// only the buyer can confirm the delivery
+		assert_noop!(
+			MarketPlace::confirm_delivery(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::confirm_delivery(RuntimeOrigin::signed(1), 1));
+		assert_eq!(last_event(), Event::MarketPlaceOrderDelivered(1, 300).into());
+		// the payment has been moved to the seller, reserved during the return window
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 300);
+		assert_eq!(Balances::free_balance(2), 1_000_000);
+		assert_eq!(Balances::reserved_balance(2), 300);
+		let order = Orders::<Test>::get(1).unwrap();
+		assert_eq!(order.status, OrderStatus::Delivered);
+		assert_eq!(order.deliveredat, Some(1));
+	});
+}
+
+// place, ship and deliver an order of the product 1 of the seller 2 to the buyer 1
+fn deliver_order(uid: u32) {
+	assert_ok!(MarketPlace::place_order(RuntimeOrigin::signed(1), uid, 1, 1));
+	assert_ok!(MarketPlace::confirm_shipment(RuntimeOrigin::signed(2), uid));
+	assert_ok!(MarketPlace::confirm_delivery(RuntimeOrigin::signed(1), uid));
+}
+
+// test the returns in the window of the seller return policy
+#[test]
+fn test_order_returned() {
+	new_test_ext().execute_with(|| {
+		// the return policy of 30 days is 300 blocks
+		setup_product(2, 1, 100);
+		deliver_order(1);
+		assert_eq!(
+			ReturnWindows::<Test>::get(1),
+			Some(ReturnWindow { closesat: 301, status: ReturnStatus::Open, respondby: None })
+		);
+		// the payment cannot be released during the window
+		assert_noop!(
+			MarketPlace::release_payment(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ReturnWindowIsOpen
+		);
+		// only the buyer can request the return
+		assert_noop!(
+			MarketPlace::request_return(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_noop!(
+			MarketPlace::approve_return(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ReturnStatusIsWrong
+		);
+		assert_ok!(MarketPlace::request_return(RuntimeOrigin::signed(1), 1));
+		// the seller must respond in 14 days, 140 blocks
+		assert_eq!(last_event(), Event::MarketPlaceReturnRequested(1, 141).into());
+		assert_noop!(
+			MarketPlace::request_return(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::ReturnStatusIsWrong
+		);
+		// only the seller can approve the return, the payment is refunded to the buyer
+		assert_noop!(
+			MarketPlace::approve_return(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::approve_return(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceReturnApproved(1, 100).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Orders::<Test>::get(1).unwrap().status, OrderStatus::Returned);
+		assert_eq!(ReturnWindows::<Test>::get(1), None);
+		// the payment of a return rejected can be released
+		deliver_order(2);
+		assert_ok!(MarketPlace::request_return(RuntimeOrigin::signed(1), 2));
+		assert_noop!(
+			MarketPlace::release_payment(RuntimeOrigin::signed(2), 2),
+			Error::<Test>::ReturnStatusIsWrong
+		);
+		assert_ok!(MarketPlace::reject_return(RuntimeOrigin::signed(2), 2));
+		assert_eq!(last_event(), Event::MarketPlaceReturnRejected(2).into());
+		assert_ok!(MarketPlace::release_payment(RuntimeOrigin::signed(2), 2));
+		assert_eq!(last_event(), Event::MarketPlacePaymentReleased(2, 100).into());
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		// the return cannot be requested after the window closed
+		deliver_order(3);
+		run_to_block(302);
+		assert_noop!(
+			MarketPlace::request_return(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::ReturnWindowClosed
+		);
+		assert_ok!(MarketPlace::release_payment(RuntimeOrigin::signed(2), 3));
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 200);
+		assert_noop!(
+			MarketPlace::release_payment(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::ReturnWindowNotFound
+		);
+		// without a return policy the payment is available to the seller on delivery
+		Sellers::<Test>::insert(
+			2,
+			SellerInfo { defaultreturnpolicy: None, ..seller_info(b"Seller Ltd") },
+		);
+		deliver_order(4);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 300);
+		assert_noop!(
+			MarketPlace::request_return(RuntimeOrigin::signed(1), 4),
+			Error::<Test>::ReturnWindowNotFound
+		);
+	});
+}
+
+// test the refund of a return claimed from the buyer when the seller does not respond
+#[test]
+fn test_return_claimed() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		deliver_order(1);
+		assert_noop!(
+			MarketPlace::claim_return(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::ReturnStatusIsWrong
+		);
+		assert_ok!(MarketPlace::request_return(RuntimeOrigin::signed(1), 1));
+		// the refund cannot be claimed till the response deadline
+		run_to_block(141);
+		assert_noop!(
+			MarketPlace::claim_return(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::ReturnResponseIsPending
+		);
+		// the payment of a return requested cannot be released, even after the window closed
+		run_to_block(302);
+		assert_noop!(
+			MarketPlace::release_payment(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ReturnStatusIsWrong
+		);
+		// the seller cannot reject the return after the deadline
+		assert_noop!(
+			MarketPlace::reject_return(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ReturnResponseExpired
+		);
+		// only the buyer can claim the refund
+		assert_noop!(
+			MarketPlace::claim_return(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::claim_return(RuntimeOrigin::signed(1), 1));
+		assert_eq!(last_event(), Event::MarketPlaceReturnApproved(1, 100).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Orders::<Test>::get(1).unwrap().status, OrderStatus::Returned);
+		assert_eq!(ReturnWindows::<Test>::get(1), None);
+	});
+}
+
+// test the cancellation of an order and the validation of the input
+#[test]
+fn test_order_cancelled() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		// order uid cannot be zero
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 0, 1, 1),
+			Error::<Test>::OrderUidCannotBeZero
+		);
+		// quantity cannot be zero
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 0),
+			Error::<Test>::OrderQuantityCannotBeZero
+		);
+		// the product must exist
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 9, 1),
+			Error::<Test>::ProductNotFound
+		);
+		// the seller cannot buy its own product
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(2), 1, 1, 1),
+			Error::<Test>::BuyerCannotBeSeller
+		);
+		assert_ok!(MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 1));
+		assert_eq!(Balances::reserved_balance(1), 100);
+		// a third party cannot cancel the order
+		assert_noop!(
+			MarketPlace::cancel_order(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		// the seller can cancel the order, the payment is returned to the buyer
+		assert_ok!(MarketPlace::cancel_order(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceOrderCancelled(1).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Orders::<Test>::get(1).unwrap().status, OrderStatus::Cancelled);
+	});
+}
+
+// test the products can be changed only from the seller who created them
+#[test]
+fn test_product_owner() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		let configuration = product_info(b"USDC", None, 200);
+		// an account not registered as seller cannot publish products
+		assert_noop!(
+			MarketPlace::create_update_product(RuntimeOrigin::signed(1), 2, configuration.clone()),
+			Error::<Test>::SellerDataNotFound
+		);
+		// another seller cannot overwrite the product
+		Sellers::<Test>::insert(3, seller_info(b"Competitor Ltd"));
+		assert_noop!(
+			MarketPlace::create_update_product(RuntimeOrigin::signed(3), 1, configuration),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		// another seller cannot destroy the product
+		assert_noop!(
+			MarketPlace::destroy_product(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		// the owner can destroy the product
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceProductDestroyed(1).into());
+		assert!(!Products::<Test>::contains_key(1));
+		assert_eq!(ProductOwner::<Test>::get(1), None);
+		assert_noop!(
+			MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ProductNotFound
+		);
+	});
+}
+
+// test the seller assigned to a product stored before the products were bound to the sellers
+#[test]
+fn test_assign_product_owner() {
+	new_test_ext().execute_with(|| {
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Products::<Test>::insert(1, product_info(b"USDC", None, 100));
+		// the product without owner cannot be changed
+		assert_noop!(
+			MarketPlace::create_update_product(
+				RuntimeOrigin::signed(2),
+				1,
+				product_info(b"USDC", None, 200)
+			),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		// the owner is assigned only from the curator origin to a registered seller
+		assert_noop!(
+			MarketPlace::assign_product_owner(RuntimeOrigin::signed(2), 1, 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			MarketPlace::assign_product_owner(RuntimeOrigin::root(), 1, 3),
+			Error::<Test>::SellerDataNotFound
+		);
+		assert_noop!(
+			MarketPlace::assign_product_owner(RuntimeOrigin::root(), 2, 2),
+			Error::<Test>::ProductNotFound
+		);
+		assert_ok!(MarketPlace::assign_product_owner(RuntimeOrigin::root(), 1, 2));
+		assert_eq!(last_event(), Event::MarketPlaceProductOwnerAssigned(1, 2).into());
+		assert_eq!(ProductOwner::<Test>::get(1), Some(2));
+		assert!(ProductsBySeller::<Test>::contains_key(2, 1));
+		assert_noop!(
+			MarketPlace::assign_product_owner(RuntimeOrigin::root(), 1, 2),
+			Error::<Test>::ProductOwnerAlreadySet
+		);
+		// the seller can update the product, the deposit is reserved with the update
+		let product = product_info(b"USDC", None, 200);
+		let deposit = MarketPlace::deposit_for(product.encoded_size());
+		assert_ok!(MarketPlace::create_update_product(RuntimeOrigin::signed(2), 1, product));
+		assert_eq!(ProductDeposits::<Test>::get(1), deposit);
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+	});
+}
+
+// test the secondary indexes of the products
+#[test]
+fn test_product_indexes() {
+	new_test_ext().execute_with(|| {
+		let usdc: CurrencyCode = bounded(b"USDC");
+		let eurc: CurrencyCode = bounded(b"EURC");
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Currencies::<Test>::insert(&usdc, currency_info(b"USD Coin"));
+		Currencies::<Test>::insert(&eurc, currency_info(b"Euro Coin"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		ProductCategories::<Test>::insert(1, 2, bounded(b"Computers"));
+		// the category must be present on chain
+		assert_noop!(
+			MarketPlace::create_update_product(
+				RuntimeOrigin::signed(2),
+				1,
+				product_info(b"USDC", Some((1, 3)), 100)
+			),
+			Error::<Test>::ProductCategoryNotFound
+		);
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product_info(b"USDC", Some((1, 1)), 100)
+		));
+		assert!(ProductsBySeller::<Test>::contains_key(2, 1));
+		assert!(ProductsByCategory::<Test>::contains_key((1, 1), 1));
+		assert!(ProductsByCurrency::<Test>::contains_key(&usdc, 1));
+		// the update moves the product to the new keys
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product_info(b"EURC", Some((1, 2)), 100)
+		));
+		assert!(!ProductsByCategory::<Test>::contains_key((1, 1), 1));
+		assert!(ProductsByCategory::<Test>::contains_key((1, 2), 1));
+		assert!(!ProductsByCurrency::<Test>::contains_key(&usdc, 1));
+		assert!(ProductsByCurrency::<Test>::contains_key(&eurc, 1));
+		assert_eq!(ProductsBySeller::<Test>::iter_key_prefix(2).collect::<Vec<u32>>(), vec![1]);
+		// the removal of the product cleans the indexes
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert!(!ProductsBySeller::<Test>::contains_key(2, 1));
+		assert!(!ProductsByCategory::<Test>::contains_key((1, 2), 1));
+		assert!(!ProductsByCurrency::<Test>::contains_key(&eurc, 1));
+	});
+}
+
+// test the migration indexing the products stored before the secondary indexes
+#[test]
+fn test_migration_v5() {
+	new_test_ext().execute_with(|| {
+		let usdc: CurrencyCode = bounded(b"USDC");
+		Products::<Test>::insert(1, product_info(b"USDC", Some((1, 1)), 100));
+		ProductOwner::<Test>::insert(1, 2);
+		Products::<Test>::insert(2, product_info(b"USDC", None, 100));
+		StorageVersion::new(4).put::<MarketPlace>();
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<MarketPlace>(), StorageVersion::new(5));
+		assert_eq!(ProductsBySeller::<Test>::iter_key_prefix(2).collect::<Vec<u32>>(), vec![1]);
+		assert!(ProductsByCategory::<Test>::contains_key((1, 1), 1));
+		let mut products = ProductsByCurrency::<Test>::iter_key_prefix(&usdc).collect::<Vec<u32>>();
+		products.sort();
+		assert_eq!(products, vec![1, 2]);
+		// the product without owner is indexed by seller when its seller is assigned
+		Sellers::<Test>::insert(3, seller_info(b"Seller Ltd"));
+		assert_ok!(MarketPlace::assign_product_owner(RuntimeOrigin::root(), 2, 3));
+		assert_eq!(ProductsBySeller::<Test>::iter_key_prefix(3).collect::<Vec<u32>>(), vec![2]);
+	});
+}
+
+// test the catalogue queries used from the runtime api
+#[test]
+fn test_query_products() {
+	new_test_ext().execute_with(|| {
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Sellers::<Test>::insert(3, seller_info(b"Other Seller"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		Currencies::<Test>::insert(bounded::<_>(b"EURC"), currency_info(b"Euro Coin"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		ProductCategories::<Test>::insert(1, 2, bounded(b"Computers"));
+		for (seller, uid, currency, category, upc) in [
+			(2, 1, b"USDC", 1, b"012345678912"),
+			(2, 2, b"EURC", 1, b"012345678929"),
+			(2, 3, b"USDC", 2, b"012345678936"),
+			(3, 4, b"USDC", 1, b"012345678943"),
+		] {
+			assert_ok!(MarketPlace::create_update_product(
+				RuntimeOrigin::signed(seller),
+				uid,
+				ProductInfo {
+					upc: bounded(upc),
+					..product_info(currency, Some((1, category)), 100)
+				}
+			));
+			assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(seller), uid));
+		}
+		let uids = |filter: ProductFilter<u64>, start: u32, limit: u32| {
+			let mut uids: Vec<u32> = MarketPlace::query_products(filter, start, limit)
+				.into_iter()
+				.map(|(uid, _)| uid)
+				.collect();
+			uids.sort();
+			uids
+		};
+		let all = ProductFilter { seller: None, category: None, currency: None };
+		assert_eq!(uids(all.clone(), 0, 10), vec![1, 2, 3, 4]);
+		assert_eq!(uids(all.clone(), 0, 3).len(), 3);
+		assert_eq!(uids(all, 3, 10).len(), 1);
+		let seller = ProductFilter { seller: Some(2), category: None, currency: None };
+		assert_eq!(uids(seller, 0, 10), vec![1, 2, 3]);
+		let filter = ProductFilter {
+			seller: Some(2),
+			category: Some((1, 1)),
+			currency: Some(bounded(b"USDC")),
+		};
+		assert_eq!(uids(filter, 0, 10), vec![1]);
+		let currency =
+			ProductFilter { seller: None, category: None, currency: Some(bounded(b"USDC")) };
+		assert_eq!(uids(currency, 0, 10), vec![1, 3, 4]);
+		assert_eq!(MarketPlace::query_seller(4), None);
+		assert_eq!(MarketPlace::query_currencies().len(), 2);
+	});
+}
+
+// test the shipping quotes computed from the shipping rates
+#[test]
+fn test_quote_shipping() {
+	new_test_ext().execute_with(|| {
+		let mut product = product_info(b"USDC", None, 100);
+		product.dimension = vec![
+			Dimension { length: 30, wide: 20, height: 5, weight: 700 },
+			Dimension { length: 10, wide: 10, height: 5, weight: 300 },
+		]
+		.try_into()
+		.unwrap();
+		product.shippers = vec![1, 2].try_into().unwrap();
+		Products::<Test>::insert(1, product);
+		let table =
+			|shipperid: u32, currency: &[u8], rates: &[(u32, u32, u128)]| ShippingRateTable {
+				shipperid,
+				origincountry: bounded(b"AE"),
+				currency: bounded(currency),
+				rates: rates
+					.iter()
+					.map(|(fromkg, tokg, rate)| ShippingRate {
+						destination: bounded(b"LR"),
+						fromkg: *fromkg,
+						tokg: *tokg,
+						rate: *rate,
+					})
+					.collect::<Vec<ShippingRate>>()
+					.try_into()
+					.unwrap(),
+			};
+		ShippingRates::<Test>::insert(1, table(1, b"AED", &[(0, 1, 10), (1, 2, 15)]));
+		ShippingRates::<Test>::insert(2, table(2, b"USDC", &[(0, 5, 12)]));
+		// shipper not listed in the product
+		ShippingRates::<Test>::insert(3, table(3, b"USDC", &[(0, 5, 1)]));
+		let mut quotes = MarketPlace::quote_shipping(1, b"LR".to_vec(), 1);
+		quotes.sort();
+		assert_eq!(quotes, vec![(1, b"AED".to_vec(), 10), (2, b"USDC".to_vec(), 12)]);
+		// 2 kg are in the second bracket of the first shipper
+		let mut quotes = MarketPlace::quote_shipping(1, b"LR".to_vec(), 2);
+		quotes.sort();
+		assert_eq!(quotes, vec![(1, b"AED".to_vec(), 15), (2, b"USDC".to_vec(), 12)]);
+		// too heavy for any bracket
+		assert!(MarketPlace::quote_shipping(1, b"LR".to_vec(), 6).is_empty());
+		// no rates for the destination
+		assert!(MarketPlace::quote_shipping(1, b"AE".to_vec(), 1).is_empty());
+		// unknown product
+		assert!(MarketPlace::quote_shipping(2, b"LR".to_vec(), 1).is_empty());
+	});
+}
+
+// test the reviews gated by the purchase receipts and the ratings aggregates
+#[test]
+fn test_product_reviews() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		// only the seller of the product can issue the receipts
+		assert_noop!(
+			MarketPlace::issue_receipt(RuntimeOrigin::signed(3), 1, 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_noop!(
+			MarketPlace::issue_receipt(RuntimeOrigin::signed(2), 1, 2),
+			Error::<Test>::BuyerCannotBeSeller
+		);
+		// a review requires a purchase receipt
+		assert_noop!(
+			MarketPlace::review_product(RuntimeOrigin::signed(1), 1, 5, None),
+			Error::<Test>::PurchaseReceiptNotFound
+		);
+		assert_ok!(MarketPlace::issue_receipt(RuntimeOrigin::signed(2), 1, 1));
+		assert_ok!(MarketPlace::issue_receipt(RuntimeOrigin::signed(2), 1, 3));
+		assert_noop!(
+			MarketPlace::review_product(RuntimeOrigin::signed(1), 1, 6, None),
+			Error::<Test>::RatingIsWrong
+		);
+		assert_ok!(MarketPlace::review_product(RuntimeOrigin::signed(1), 1, 5, None));
+		assert_ok!(MarketPlace::review_product(
+			RuntimeOrigin::signed(3),
+			1,
+			2,
+			Some(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec())
+		));
+		assert_eq!(last_event(), Event::MarketPlaceSellerRatingUpdated(2, 2, 7).into());
+		assert_eq!(ProductRatings::<Test>::get(1), RatingSummary { count: 2, sum: 7 });
+		// a new review of the same buyer replaces the previous rating
+		assert_ok!(MarketPlace::review_product(RuntimeOrigin::signed(3), 1, 4, None));
+		assert_eq!(ProductRatings::<Test>::get(1), RatingSummary { count: 2, sum: 9 });
+		assert_eq!(SellerRatings::<Test>::get(2), RatingSummary { count: 2, sum: 9 });
+		assert_eq!(Reviews::<Test>::get(1, 3).unwrap().reviewhash, None);
+	});
+}
+
+// test the stock of the product variants
+#[test]
+fn test_product_stock() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		let mut product = product_info(b"USDC", None, 10);
+		product.colors = vec![1, 2].try_into().unwrap();
+		product.sizes = vec![3].try_into().unwrap();
+		Products::<Test>::insert(2, product);
+		ProductOwner::<Test>::insert(2, 2);
+		// only the seller can set the stock
+		assert_noop!(
+			MarketPlace::set_stock(RuntimeOrigin::signed(1), 1, 0, 0, 10),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		// the variants must be declared in the product
+		assert_noop!(
+			MarketPlace::set_stock(RuntimeOrigin::signed(2), 1, 1, 0, 10),
+			Error::<Test>::VariantNotFound
+		);
+		assert_noop!(
+			MarketPlace::set_stock(RuntimeOrigin::signed(2), 2, 3, 3, 10),
+			Error::<Test>::VariantNotFound
+		);
+		assert_ok!(MarketPlace::set_stock(RuntimeOrigin::signed(2), 1, 0, 0, 10));
+		assert_ok!(MarketPlace::set_stock(RuntimeOrigin::signed(2), 2, 2, 3, 5));
+		assert_ok!(MarketPlace::adjust_stock(RuntimeOrigin::signed(2), 2, 2, 3, -2));
+		assert_eq!(last_event(), Event::MarketPlaceStockUpdated(2, 2, 3, 3).into());
+		assert_noop!(
+			MarketPlace::adjust_stock(RuntimeOrigin::signed(2), 2, 2, 3, -4),
+			Error::<Test>::OutOfStock
+		);
+		// the sales are deducted from the stock
+		assert_ok!(MarketPlace::record_sale(RuntimeOrigin::signed(2), 2, 2, 3, 3));
+		assert_eq!(Stock::<Test>::get((2, 2, 3)), 0);
+		assert_noop!(
+			MarketPlace::record_sale(RuntimeOrigin::signed(2), 2, 2, 3, 1),
+			Error::<Test>::OutOfStock
+		);
+		assert_noop!(
+			MarketPlace::record_sale(RuntimeOrigin::signed(2), 1, 0, 0, 0),
+			Error::<Test>::QuantityCannotBeZero
+		);
+		assert_ok!(MarketPlace::record_sale(RuntimeOrigin::signed(2), 1, 0, 0, 4));
+		assert_eq!(Stock::<Test>::get((1, 0, 0)), 6);
+		// the stock is removed with the product
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Stock::<Test>::get((1, 0, 0)), 0);
+	});
+}
+
+// test the escrow released from the buyer
+#[test]
+fn test_escrow_released() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_ok!(MarketPlace::open_escrow(RuntimeOrigin::signed(1), 1, 1));
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_noop!(
+			MarketPlace::open_escrow(RuntimeOrigin::signed(1), 1, 1),
+			Error::<Test>::EscrowAlreadyPresent
+		);
+		// only the buyer can release the funds
+		assert_noop!(
+			MarketPlace::release_escrow(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::release_escrow(RuntimeOrigin::signed(1), 1));
+		assert_eq!(last_event(), Event::MarketPlaceEscrowReleased(1, 100).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 1_000_100);
+		assert_eq!(Escrows::<Test>::get(1).unwrap().status, EscrowStatus::Released);
+		// a released escrow cannot be disputed
+		assert_noop!(
+			MarketPlace::open_dispute(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::EscrowStatusIsWrong
+		);
+	});
+}
+
+// test the dispute resolved from the arbitrators
+#[test]
+fn test_escrow_dispute() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_ok!(MarketPlace::open_escrow(RuntimeOrigin::signed(1), 1, 1));
+		// the evidences can be submitted only during the dispute
+		assert_noop!(
+			MarketPlace::submit_evidence(RuntimeOrigin::signed(1), 1, b"hash".to_vec(), None),
+			Error::<Test>::EscrowStatusIsWrong
+		);
+		assert_noop!(
+			MarketPlace::open_dispute(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::open_dispute(RuntimeOrigin::signed(2), 1));
+		assert_ok!(MarketPlace::submit_evidence(
+			RuntimeOrigin::signed(1),
+			1,
+			b"hash".to_vec(),
+			Some(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec())
+		));
+		assert_noop!(
+			MarketPlace::submit_evidence(
+				RuntimeOrigin::signed(2),
+				1,
+				b"hash".to_vec(),
+				Some(b"Qm".to_vec())
+			),
+			Error::<Test>::EvidenceIpfsAddressIsWrong
+		);
+		assert_eq!(DisputeEvidences::<Test>::get(1).len(), 1);
+		// only the arbitrators can resolve the dispute
+		assert_noop!(
+			MarketPlace::resolve_dispute(RuntimeOrigin::signed(1), 1, Percent::from_percent(70)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(MarketPlace::resolve_dispute(
+			RuntimeOrigin::root(),
+			1,
+			Percent::from_percent(70)
+		));
+		assert_eq!(last_event(), Event::MarketPlaceDisputeResolved(1, 70, 30).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 999_970);
+		assert_eq!(Balances::free_balance(2), 1_000_030);
+	});
+}
+
+// test the json parser used from the validations
+#[test]
+fn test_json_parser() {
+	let value =
+		json::parse(br#"{"name":"Seller \"Ltd\"","websites":[{"name":"x"}],"price":100}"#).unwrap();
+	assert_eq!(value.get_bytes("name"), br#"Seller "Ltd""#.to_vec());
+	assert_eq!(value.get_bytes("price"), b"100".to_vec());
+	assert_eq!(value.get_array("websites").len(), 1);
+	assert!(value.get_bytes("missing").is_empty());
+	assert!(value.get_array("name").is_empty());
+	let value = json::parse(r#"{"a":"è\u00e8\ud83d\ude00"}"#.as_bytes()).unwrap();
+	assert_eq!(value.get_bytes("a"), "èè😀".as_bytes());
+	assert_eq!(json::parse(br#"{"a":1,}"#), Err(JsonError::UnexpectedCharacter(7)));
+	assert_eq!(json::parse(br#"{"a":01}"#), Err(JsonError::UnexpectedCharacter(6)));
+	assert_eq!(json::parse(br#"{"a":1.}"#), Err(JsonError::InvalidNumber(5)));
+	assert_eq!(json::parse(br#"{"a":"\x"}"#), Err(JsonError::InvalidString(6)));
+	assert_eq!(json::parse(br#"{"a":1,"a":2}"#), Err(JsonError::DuplicateKey(7)));
+	assert_eq!(json::parse(br#"{"a":1}x"#), Err(JsonError::TrailingCharacters(7)));
+	assert_eq!(json::parse(br#"{"a":"b"#), Err(JsonError::UnexpectedEnd));
+	assert_eq!(json::parse(b"[[[[[[[[[1]]]]]]]]]"), Err(JsonError::TooDeep));
+	assert_eq!(json::parse(&[b' '; json::MAX_JSON_LENGTH + 1]), Err(JsonError::TooLong));
+}
+
+// test the validation of the seller data and the updates from the proxy account
+#[test]
+fn test_seller_validation() {
+	new_test_ext().execute_with(|| {
+		IsoCountries::<Test>::insert(bounded::<_>(b"LR"), bounded::<_>(b"Liberia"));
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, seller_info(b"Bob")),
+			Error::<Test>::SellerNameTooShort
+		);
+		// the categories must be present on chain
+		assert_noop!(
+			MarketPlace::create_update_seller(
+				RuntimeOrigin::signed(1),
+				None,
+				seller_info(b"Seller Ltd")
+			),
+			Error::<Test>::ProductCategoryNotFound
+		);
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		let mut info = seller_info(b"Seller Ltd");
+		info.emailinfo = bounded(b"info.seller.com");
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info),
+			Error::<Test>::SellerInfoEmailIsWrong
+		);
+		let mut info = seller_info(b"Seller Ltd");
+		info.websites = vec![bounded(b"www")].try_into().unwrap();
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info),
+			Error::<Test>::SellerWebsiteUrlIsWrong
+		);
+		// a third party cannot create a seller for another account
+		assert_noop!(
+			MarketPlace::create_update_seller(
+				RuntimeOrigin::signed(3),
+				Some(1),
+				seller_info(b"Seller Ltd")
+			),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		// the seller sets the proxy account
+		let mut info = seller_info(b"Seller Ltd");
+		info.proxyaccount = Some(3);
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info.clone()));
+		assert_eq!(last_event(), Event::MarketPlaceSellerCreated(1, info.clone()).into());
+		// only the proxy account can update the seller data on behalf of the seller
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(2), Some(1), info.clone()),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		info.city = bounded(b"Buchanan");
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(3), Some(1), info));
+		assert_eq!(Sellers::<Test>::get(1).unwrap().city.to_vec(), b"Buchanan".to_vec());
+	});
+}
+
+// test the migration of the records stored as json to the typed records
+#[test]
+fn test_migration_v1() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<MarketPlace>();
+		let put = |key: Vec<u8>, data: &[u8]| unhashed::put(&key, &data.to_vec());
+		put(
+			Sellers::<Test>::hashed_key_for(1),
+			br#"{"sellertype":2,"name":"Seller Ltd","address":"Broad Street 1","zip":"1000","city":"Monrovia","emailinfo":"info@seller.com","emailsupport":"support@seller.com","categories":[{"department":1,"category":2}],"countries":[{"country":"LR","inout":0},{"country":"AE","inout":1}],"defaultreturnpolicy":30}"#,
+		);
+		put(
+			Currencies::<Test>::hashed_key_for(bounded::<_>(b"USDC")),
+			br#"{"name":"USD Coin","category":"c","country":"","blockchain":"Ethereum"}"#,
+		);
+		put(
+			Products::<Test>::hashed_key_for(1),
+			br#"{"description":"Laptop","price":100,"currency":"USDC","department":1,"category":2,"media":[{"description":"front","filename":"front.jpg","ipfs":"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"}],"colors":[1,2],"dimension":[{"length":30,"wide":20,"height":5,"weight":700}],"shippers":[1],"upc":"012345678905"}"#,
+		);
+		// wrong json and fields too long are removed
+		put(Products::<Test>::hashed_key_for(2), br#"{"description":"Laptop","#);
+		put(
+			Products::<Test>::hashed_key_for(3),
+			br#"{"description":"Laptop","currency":"USDCOIN"}"#,
+		);
+		put(
+			ShippingRates::<Test>::hashed_key_for(1),
+			br#"{"shipperid":1,"origincountry":"AE","currency":"AED","rates":[{"destination":"LR","fromkg":0,"tokg":1,"rate":10}]}"#,
+		);
+		// the entries of the other maps with a key or a value out of the bounds are removed
+		let vecput = |key: Vec<u8>, data: Vec<u8>| unhashed::put(&key, &data);
+		vecput(IsoCountries::<Test>::hashed_key_for(bounded::<_>(b"LR")), b"Liberia".to_vec());
+		vecput(IsoCountries::<Test>::hashed_key_for(bounded::<_>(b"AE")), vec![b'a'; 65]);
+		vecput(LoginData::<Test>::hashed_key_for(bounded::<_>(b"hash")), vec![1; 1024]);
+		vecput(EmailEncryptedSeed::<Test>::hashed_key_for(bounded::<_>(b"hash")), vec![1; 1025]);
+		let longkey = [
+			LoginData::<Test>::final_prefix().to_vec(),
+			Blake2_128Concat::hash(&vec![b'h'; 129].encode()),
+		]
+		.concat();
+		vecput(longkey.clone(), vec![1; 32]);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<MarketPlace>(), StorageVersion::new(1));
+		assert_eq!(IsoCountries::<Test>::get(bounded::<_>(b"LR")).to_vec(), b"Liberia".to_vec());
+		assert!(!IsoCountries::<Test>::contains_key(bounded::<_>(b"AE")));
+		assert_eq!(LoginData::<Test>::get(bounded::<_>(b"hash")).len(), 1024);
+		assert!(!EmailEncryptedSeed::<Test>::contains_key(bounded::<_>(b"hash")));
+		assert!(unhashed::get_raw(&longkey).is_none());
+		let seller = Sellers::<Test>::get(1).unwrap();
+		assert_eq!(seller.sellertype, SellerType::Freelancer);
+		assert_eq!(seller.categories.to_vec(), vec![(1, 2)]);
+		assert!(seller.countries[1].excluded);
+		assert_eq!(seller.defaultreturnpolicy, Some(30));
+		assert_eq!(seller.proxyaccount, None);
+		let currency = Currencies::<Test>::get(bounded::<_>(b"USDC")).unwrap();
+		assert_eq!(currency.category, CurrencyCategory::Crypto);
+		assert_eq!(currency.country, None);
+		let product = Products::<Test>::get(1).unwrap();
+		assert_eq!(product.price, 100);
+		assert_eq!(product.category, Some((1, 2)));
+		assert_eq!(product.colors.to_vec(), vec![1, 2]);
+		assert_eq!(product.dimension[0].weight, 700);
+		assert!(!Products::<Test>::contains_key(2));
+		assert!(!Products::<Test>::contains_key(3));
+		assert_eq!(ShippingRates::<Test>::get(1).unwrap().rates[0].rate, 10);
+		assert_eq!(MarketPlace::quote_shipping(1, b"LR".to_vec(), 1), vec![(1, b"AED".to_vec(), 10)]);
+	});
+}
+
+// test the deposits reserved for the data of the sellers and the products
+#[test]
+fn test_deposits() {
+	new_test_ext().execute_with(|| {
+		IsoCountries::<Test>::insert(bounded::<_>(b"LR"), bounded::<_>(b"Liberia"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		let seller = seller_info(b"Seller Ltd");
+		let sellerdeposit = MarketPlace::deposit_for(seller.encoded_size());
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, seller));
+		assert_eq!(SellerDeposits::<Test>::get(1), sellerdeposit);
+		assert_eq!(Balances::reserved_balance(1), sellerdeposit);
+		// the deposit is adjusted to the size of the data updated
+		let seller = seller_info(b"Seller Limited Liability Company");
+		let sellerdeposit = MarketPlace::deposit_for(seller.encoded_size());
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, seller));
+		assert_eq!(Balances::reserved_balance(1), sellerdeposit);
+		let product = product_info(b"USDC", Some((1, 1)), 100);
+		let productdeposit = MarketPlace::deposit_for(product.encoded_size());
+		assert_ok!(MarketPlace::create_update_product(RuntimeOrigin::signed(1), 1, product));
+		assert_eq!(ProductDeposits::<Test>::get(1), productdeposit);
+		assert_eq!(Balances::reserved_balance(1), sellerdeposit + productdeposit);
+		// the deposits are returned when the data is removed
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Balances::reserved_balance(1), sellerdeposit);
+		assert!(!ProductDeposits::<Test>::contains_key(1));
+		assert_ok!(MarketPlace::destroy_seller(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		// the deposit must be available
+		assert_noop!(
+			MarketPlace::create_update_seller(
+				RuntimeOrigin::signed(4),
+				None,
+				seller_info(b"Seller Ltd")
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+// test the reference data maintained from the curators of the domain
+#[test]
+fn test_curators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			MarketPlace::create_iso_country(
+				RuntimeOrigin::signed(1),
+				b"LR".to_vec(),
+				b"Liberia".to_vec()
+			),
+			Error::<Test>::SignerIsNotCurator
+		);
+		// only the curator origin can manage the curators
+		assert_noop!(
+			MarketPlace::add_curator(RuntimeOrigin::signed(1), 1, CuratorDomain::Countries),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(MarketPlace::add_curator(RuntimeOrigin::root(), 1, CuratorDomain::Countries));
+		assert_eq!(
+			last_event(),
+			Event::MarketPlaceCuratorAdded(1, CuratorDomain::Countries).into()
+		);
+		assert_noop!(
+			MarketPlace::add_curator(RuntimeOrigin::root(), 1, CuratorDomain::Countries),
+			Error::<Test>::CuratorAlreadyPresent
+		);
+		assert_ok!(MarketPlace::create_iso_country(
+			RuntimeOrigin::signed(1),
+			b"LR".to_vec(),
+			b"Liberia".to_vec()
+		));
+		// the curator is limited to its domain
+		assert_noop!(
+			MarketPlace::create_currency(
+				RuntimeOrigin::signed(1),
+				b"USDC".to_vec(),
+				currency_info(b"USD Coin")
+			),
+			Error::<Test>::SignerIsNotCurator
+		);
+		// the curator origin can maintain all the domains
+		assert_ok!(MarketPlace::create_currency(
+			RuntimeOrigin::root(),
+			b"USDC".to_vec(),
+			currency_info(b"USD Coin")
+		));
+		assert_ok!(MarketPlace::remove_curator(RuntimeOrigin::root(), 1, CuratorDomain::Countries));
+		assert_noop!(
+			MarketPlace::remove_curator(RuntimeOrigin::root(), 1, CuratorDomain::Countries),
+			Error::<Test>::CuratorNotFound
+		);
+		assert_noop!(
+			MarketPlace::destroy_iso_country(RuntimeOrigin::signed(1), b"LR".to_vec()),
+			Error::<Test>::SignerIsNotCurator
+		);
+	});
+}
+
+// test the weight of the calls counting the references is the one of the references counted
+#[test]
+fn test_reference_weights() {
+	new_test_ext().execute_with(|| {
+		IsoCountries::<Test>::insert(bounded::<_>(b"LR"), bounded::<_>(b"Liberia"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		// the seller references a category and a country
+		let info = MarketPlace::create_update_seller(
+			RuntimeOrigin::signed(2),
+			None,
+			seller_info(b"Seller Ltd"),
+		)
+		.unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::create_update_seller(2, 0)));
+		let info = MarketPlace::create_update_seller(
+			RuntimeOrigin::signed(2),
+			None,
+			seller_info(b"Seller Ltd"),
+		)
+		.unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::create_update_seller(2, 2)));
+		// the product references a currency and a category
+		let product = product_info(b"USDC", Some((1, 1)), 100);
+		let info = MarketPlace::create_update_product(RuntimeOrigin::signed(2), 1, product.clone())
+			.unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::create_update_product(2, 0)));
+		let info =
+			MarketPlace::create_update_product(RuntimeOrigin::signed(2), 1, product).unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::create_update_product(2, 2)));
+		assert!(<() as WeightInfo>::create_update_product(2, 2)
+			.all_lt(<() as WeightInfo>::create_update_product(2, MAX_PRODUCT_REFERENCES)));
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		let info = MarketPlace::destroy_seller(RuntimeOrigin::signed(2)).unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::destroy_seller(2)));
+	});
+}
+
+// test the reference data cannot be removed while it's in use
+#[test]
+fn test_reference_integrity() {
+	new_test_ext().execute_with(|| {
+		let root = RuntimeOrigin::root;
+		assert_ok!(MarketPlace::create_iso_country(root(), b"LR".to_vec(), b"Liberia".to_vec()));
+		assert_ok!(MarketPlace::create_currency(
+			root(),
+			b"USDC".to_vec(),
+			currency_info(b"USD Coin")
+		));
+		assert_ok!(MarketPlace::create_product_department(root(), 1, b"Electronics".to_vec()));
+		assert_ok!(MarketPlace::create_product_category(root(), 1, 1, b"Laptops".to_vec()));
+		assert_ok!(MarketPlace::create_product_color(root(), 1, b"Black".to_vec()));
+		assert_ok!(MarketPlace::create_update_seller(
+			RuntimeOrigin::signed(2),
+			None,
+			seller_info(b"Seller Ltd")
+		));
+		let mut product = product_info(b"USDC", Some((1, 1)), 100);
+		product.colors = vec![1].try_into().unwrap();
+		assert_ok!(MarketPlace::create_update_product(RuntimeOrigin::signed(2), 1, product));
+		assert_eq!(ReferenceCount::<Test>::get(ReferenceKey::Category(1, 1)), 2);
+		assert_eq!(ReferenceCount::<Test>::get(ReferenceKey::Currency(bounded(b"USDC"))), 1);
+		assert_noop!(
+			MarketPlace::destroy_iso_country(root(), b"LR".to_vec()),
+			Error::<Test>::InUse
+		);
+		assert_noop!(MarketPlace::destroy_currency(root(), b"USDC".to_vec()), Error::<Test>::InUse);
+		assert_noop!(MarketPlace::destroy_product_category(root(), 1, 1), Error::<Test>::InUse);
+		assert_noop!(MarketPlace::destroy_product_department(root(), 1), Error::<Test>::InUse);
+		assert_noop!(MarketPlace::destroy_product_color(root(), 1), Error::<Test>::InUse);
+		// the references of the product are released on update and removal
+		let product = product_info(b"USDC", Some((1, 1)), 100);
+		assert_ok!(MarketPlace::create_update_product(RuntimeOrigin::signed(2), 1, product));
+		assert_ok!(MarketPlace::destroy_product_color(root(), 1));
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(ReferenceCount::<Test>::get(ReferenceKey::Category(1, 1)), 1);
+		assert_ok!(MarketPlace::destroy_currency(root(), b"USDC".to_vec()));
+		assert_ok!(MarketPlace::destroy_seller(RuntimeOrigin::signed(2)));
+		assert_ok!(MarketPlace::destroy_product_category(root(), 1, 1));
+		assert_ok!(MarketPlace::destroy_product_department(root(), 1));
+		assert_ok!(MarketPlace::destroy_iso_country(root(), b"LR".to_vec()));
+		assert_eq!(ReferenceCount::<Test>::iter().count(), 0);
+	});
+}
+
+// test the shippers cannot be removed while their shipping rates are present
+#[test]
+fn test_destroy_shipping_rates() {
+	new_test_ext().execute_with(|| {
+		let root = RuntimeOrigin::root;
+		assert_ok!(MarketPlace::create_iso_country(root(), b"AE".to_vec(), b"Emirates".to_vec()));
+		assert_ok!(MarketPlace::create_iso_country(root(), b"LR".to_vec(), b"Liberia".to_vec()));
+		assert_ok!(MarketPlace::create_currency(
+			root(),
+			b"USDC".to_vec(),
+			currency_info(b"USD Coin")
+		));
+		let shipper = ShipperInfo {
+			name: bounded(b"DHL"),
+			website: bounded(b"www.dhl.com"),
+			origincountries: vec![bounded(b"AE")].try_into().unwrap(),
+			destinationcountries: BoundedVec::default(),
+		};
+		assert_ok!(MarketPlace::create_shipper(root(), 1, shipper));
+		let rates = ShippingRateTable {
+			shipperid: 1,
+			origincountry: bounded(b"AE"),
+			currency: bounded(b"USDC"),
+			rates: vec![ShippingRate { destination: bounded(b"LR"), fromkg: 0, tokg: 5, rate: 12 }]
+				.try_into()
+				.unwrap(),
+		};
+		assert_ok!(MarketPlace::create_shipping_rates(root(), 1, rates));
+		assert_noop!(MarketPlace::destroy_shipper(root(), 1), Error::<Test>::InUse);
+		assert_noop!(
+			MarketPlace::destroy_iso_country(root(), b"LR".to_vec()),
+			Error::<Test>::InUse
+		);
+		// the shipping rates are removed, not the shipper
+		assert_ok!(MarketPlace::destroy_shipping_rates(root(), 1));
+		assert_eq!(last_event(), Event::MarketShippingRateDestroyed(1).into());
+		assert!(!ShippingRates::<Test>::contains_key(1));
+		assert!(Shippers::<Test>::contains_key(1));
+		assert_ok!(MarketPlace::destroy_iso_country(root(), b"LR".to_vec()));
+		assert_ok!(MarketPlace::destroy_shipper(root(), 1));
+		assert_ok!(MarketPlace::destroy_iso_country(root(), b"AE".to_vec()));
+	});
+}
+
+// test the english auction, the outbid bidders are refunded and the highest bid is paid to the
+// seller at the end block
+#[test]
+fn test_english_auction() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		let english = AuctionType::English { reserveprice: 500 };
+		// only the seller of the product can auction it
+		assert_noop!(
+			MarketPlace::create_auction(RuntimeOrigin::signed(3), 1, 1, english, 10),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_noop!(
+			MarketPlace::create_auction(RuntimeOrigin::signed(2), 1, 1, english, 0),
+			Error::<Test>::AuctionDurationCannotBeZero
+		);
+		assert_ok!(MarketPlace::create_auction(RuntimeOrigin::signed(2), 1, 1, english, 10));
+		assert_eq!(last_event(), Event::MarketPlaceAuctionCreated(1, 2, 1, 11).into());
+		assert_noop!(
+			MarketPlace::bid(RuntimeOrigin::signed(2), 1, 600),
+			Error::<Test>::BuyerCannotBeSeller
+		);
+		assert_noop!(MarketPlace::bid(RuntimeOrigin::signed(1), 1, 400), Error::<Test>::BidTooLow);
+		assert_ok!(MarketPlace::bid(RuntimeOrigin::signed(1), 1, 500));
+		assert_eq!(Balances::reserved_balance(1), 500);
+		assert_noop!(MarketPlace::bid(RuntimeOrigin::signed(3), 1, 500), Error::<Test>::BidTooLow);
+		// the outbid bidder is refunded
+		assert_ok!(MarketPlace::bid(RuntimeOrigin::signed(3), 1, 700));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(3), 700);
+		// the auction is settled at the end block
+		run_to_block(10);
+		assert!(Auctions::<Test>::contains_key(1));
+		run_to_block(11);
+		assert_eq!(last_event(), Event::MarketPlaceAuctionSettled(1, 2, 3, 1, 700).into());
+		assert!(!Auctions::<Test>::contains_key(1));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 1_000_000 - 700);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 700);
+		assert_noop!(
+			MarketPlace::bid(RuntimeOrigin::signed(1), 1, 800),
+			Error::<Test>::AuctionNotFound
+		);
+		// the auction without bids is closed
+		assert_ok!(MarketPlace::create_auction(RuntimeOrigin::signed(2), 2, 1, english, 5));
+		run_to_block(16);
+		assert_eq!(last_event(), Event::MarketPlaceAuctionClosed(2).into());
+	});
+}
+
+// test the auctions exceeding the weight limit of the block are settled in the next block
+#[test]
+fn test_auctions_weight_limit() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		setup_product(2, 2, 100);
+		let english = AuctionType::English { reserveprice: 500 };
+		assert_ok!(MarketPlace::create_auction(RuntimeOrigin::signed(2), 1, 1, english, 10));
+		assert_ok!(MarketPlace::create_auction(RuntimeOrigin::signed(2), 2, 2, english, 10));
+		assert_ok!(MarketPlace::bid(RuntimeOrigin::signed(1), 1, 500));
+		assert_ok!(MarketPlace::bid(RuntimeOrigin::signed(3), 2, 600));
+		// the weight limit allows a single settlement
+		MaxInitializeWeight::set(RocksDbWeight::get().reads_writes(6, 5));
+		run_to_block(11);
+		assert_eq!(last_event(), Event::MarketPlaceAuctionSettled(1, 2, 1, 1, 500).into());
+		assert!(Auctions::<Test>::contains_key(2));
+		assert_eq!(AuctionsEndingAt::<Test>::get(12).to_vec(), vec![2]);
+		assert_eq!(Balances::reserved_balance(3), 600);
+		run_to_block(12);
+		assert_eq!(last_event(), Event::MarketPlaceAuctionSettled(2, 2, 3, 2, 600).into());
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 1_100);
+	});
+}
+
+// test the dutch auction, the price decreases every block and the first bid wins
+#[test]
+fn test_dutch_auction() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_noop!(
+			MarketPlace::create_auction(
+				RuntimeOrigin::signed(2),
+				1,
+				1,
+				AuctionType::Dutch { startprice: 100, decrement: 10, floorprice: 100 },
+				10
+			),
+			Error::<Test>::DutchAuctionPricesAreWrong
+		);
+		let dutch = AuctionType::Dutch { startprice: 1000, decrement: 100, floorprice: 400 };
+		assert_ok!(MarketPlace::create_auction(RuntimeOrigin::signed(2), 1, 1, dutch, 20));
+		run_to_block(4);
+		let auction = Auctions::<Test>::get(1).unwrap();
+		assert_eq!(MarketPlace::auction_price(&auction, 4), 700);
+		assert_eq!(MarketPlace::auction_price(&auction, 15), 400);
+		assert_noop!(MarketPlace::bid(RuntimeOrigin::signed(1), 1, 600), Error::<Test>::BidTooLow);
+		// the current price is paid
+		assert_ok!(MarketPlace::bid(RuntimeOrigin::signed(1), 1, 800));
+		assert_eq!(last_event(), Event::MarketPlaceAuctionSettled(1, 2, 1, 1, 700).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 700);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 700);
+		assert!(!Auctions::<Test>::contains_key(1));
+		// the settlement scheduled is skipped
+		run_to_block(21);
+		assert_eq!(last_event(), Event::MarketPlaceAuctionSettled(1, 2, 1, 1, 700).into());
+	});
+}
+
+// test the coupons of the sellers, the discounted price is paid to the seller within the limits
+// of redemptions
+#[test]
+fn test_coupons() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 1000);
+		setup_product(2, 2, 500);
+		let couponhash = BlakeTwo256::hash(b"SUMMER10");
+		let coupon = Coupon {
+			discount: Discount::Percentage(Percent::from_percent(10)),
+			validfrom: 1,
+			validto: 10,
+			maxredemptions: Some(2),
+			maxredemptionsperbuyer: Some(1),
+			scope: CouponScope::Products(vec![1].try_into().unwrap()),
+		};
+		// only the products of the seller can be listed
+		assert_noop!(
+			MarketPlace::create_coupon(
+				RuntimeOrigin::signed(2),
+				couponhash,
+				Coupon {
+					scope: CouponScope::Products(vec![3].try_into().unwrap()),
+					..coupon.clone()
+				}
+			),
+			Error::<Test>::ProductNotFound
+		);
+		assert_ok!(MarketPlace::create_coupon(RuntimeOrigin::signed(2), couponhash, coupon));
+		assert_eq!(last_event(), Event::MarketPlaceCouponCreated(2, couponhash).into());
+		assert_noop!(
+			MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 1, b"WINTER10".to_vec()),
+			Error::<Test>::CouponNotFound
+		);
+		assert_noop!(
+			MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 2, b"SUMMER10".to_vec()),
+			Error::<Test>::CouponNotValidForProduct
+		);
+		assert_ok!(MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 1, b"SUMMER10".to_vec()));
+		assert_eq!(last_event(), Event::MarketPlaceCouponRedeemed(couponhash, 1, 2, 1, 900).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 900);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 900);
+		assert!(PurchaseReceipts::<Test>::contains_key(1, 1));
+		// the limits of redemptions are checked
+		assert_noop!(
+			MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 1, b"SUMMER10".to_vec()),
+			Error::<Test>::CouponRedemptionsExceeded
+		);
+		assert_ok!(MarketPlace::redeem_coupon(RuntimeOrigin::signed(3), 1, b"SUMMER10".to_vec()));
+		let nonce = CouponNonces::<Test>::get(2, couponhash).unwrap();
+		assert_eq!(CouponRedemptions::<Test>::get(nonce), 2);
+		assert_eq!(CouponBuyerRedemptions::<Test>::get(nonce, 3), 1);
+		// the coupon expires after the last block of validity, the redemptions are removed with
+		// the coupon and a coupon created again with the same code starts from zero
+		assert_ok!(MarketPlace::destroy_coupon(RuntimeOrigin::signed(2), couponhash));
+		assert!(!CouponRedemptions::<Test>::contains_key(nonce));
+		assert!(!CouponBuyerRedemptions::<Test>::contains_key(nonce, 1));
+		assert!(!CouponBuyerRedemptions::<Test>::contains_key(nonce, 3));
+		let coupon = Coupon {
+			discount: Discount::Fixed(600),
+			validfrom: 1,
+			validto: 10,
+			maxredemptions: None,
+			maxredemptionsperbuyer: None,
+			scope: CouponScope::AllProducts,
+		};
+		assert_ok!(MarketPlace::create_coupon(RuntimeOrigin::signed(2), couponhash, coupon));
+		let newnonce = CouponNonces::<Test>::get(2, couponhash).unwrap();
+		assert_ne!(newnonce, nonce);
+		assert_eq!(CouponRedemptions::<Test>::get(newnonce), 0);
+		// the product cannot be given for free
+		assert_noop!(
+			MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 2, b"SUMMER10".to_vec()),
+			Error::<Test>::DiscountedPriceCannotBeZero
+		);
+		assert_ok!(MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 1, b"SUMMER10".to_vec()));
+		assert_eq!(last_event(), Event::MarketPlaceCouponRedeemed(couponhash, 1, 2, 1, 400).into());
+		assert_eq!(CouponRedemptions::<Test>::get(newnonce), 1);
+		// the counters of the buyers are stored only for the coupons limiting them
+		assert!(!CouponBuyerRedemptions::<Test>::contains_key(newnonce, 1));
+		System::set_block_number(11);
+		assert_noop!(
+			MarketPlace::redeem_coupon(RuntimeOrigin::signed(1), 1, b"SUMMER10".to_vec()),
+			Error::<Test>::CouponIsNotValid
+		);
+	});
+}
+
+// test the subscriptions charged every billing period till the buyer unsubscribes or the balance
+// is insufficient
+#[test]
+fn test_subscriptions() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 400_000);
+		assert_noop!(
+			MarketPlace::subscribe(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::ProductIsNotSubscription
+		);
+		assert_noop!(
+			MarketPlace::set_subscription_plan(RuntimeOrigin::signed(3), 1, Some(10)),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::set_subscription_plan(RuntimeOrigin::signed(2), 1, Some(10)));
+		// the first period is charged immediately
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(1), 1));
+		assert_eq!(last_event(), Event::MarketPlaceSubscribed(1, 1, 400_000).into());
+		assert_eq!(Balances::free_balance(1), 600_000);
+		assert_noop!(
+			MarketPlace::subscribe(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::AlreadySubscribed
+		);
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(3), 1));
+		assert_eq!(BillingQueue::<Test>::get(11).len(), 2);
+		assert_eq!(SubscriberCount::<Test>::get(1), 2);
+		// the next period is charged in the billing block
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 200_000);
+		assert_eq!(Balances::free_balance(3), 200_000);
+		assert_eq!(Subscriptions::<Test>::get(1, 1).unwrap().nextbilling, 21);
+		assert_ok!(MarketPlace::unsubscribe(RuntimeOrigin::signed(3), 1));
+		assert_eq!(SubscriberCount::<Test>::get(1), 1);
+		// the subscription lapses when the balance is insufficient
+		run_to_block(21);
+		assert_eq!(last_event(), Event::MarketPlaceSubscriptionLapsed(1, 1).into());
+		assert!(!Subscriptions::<Test>::contains_key(1, 1));
+		assert_eq!(SubscriberCount::<Test>::get(1), 0);
+		assert_eq!(Balances::free_balance(1), 200_000);
+		assert_eq!(Balances::free_balance(3), 200_000);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 1_200_000);
+		assert_noop!(
+			MarketPlace::unsubscribe(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SubscriptionNotFound
+		);
+		// the subscribers of a product are limited
+		SubscriberCount::<Test>::insert(1, MAX_SUBSCRIBERS);
+		assert_noop!(
+			MarketPlace::subscribe(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::TooManySubscribers
+		);
+	});
+}
+
+// test the charges exceeding the weight limit of the block are delayed to the next block
+#[test]
+fn test_subscriptions_weight_limit() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_ok!(MarketPlace::set_subscription_plan(RuntimeOrigin::signed(2), 1, Some(10)));
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(1), 1));
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(3), 1));
+		// the weight limit allows a single charge
+		MaxInitializeWeight::set(
+			RocksDbWeight::get().reads_writes(8 + MAX_BILLING_DELAY as u64, 6),
+		);
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 200);
+		assert_eq!(Balances::free_balance(3), 1_000_000 - 100);
+		assert_eq!(Subscriptions::<Test>::get(1, 1).unwrap().nextbilling, 21);
+		assert_eq!(Subscriptions::<Test>::get(1, 3).unwrap().nextbilling, 12);
+		assert_eq!(BillingQueue::<Test>::get(12).to_vec(), vec![(1, 3)]);
+		run_to_block(12);
+		assert_eq!(last_event(), Event::MarketPlaceSubscriptionCharged(1, 3, 100).into());
+		assert_eq!(Balances::free_balance(3), 1_000_000 - 200);
+		assert_eq!(Subscriptions::<Test>::get(1, 3).unwrap().nextbilling, 22);
+	});
+}
+
+// test a subscription is not charged when its next charge cannot be scheduled
+#[test]
+fn test_subscriptions_queue_full() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_ok!(MarketPlace::set_subscription_plan(RuntimeOrigin::signed(2), 1, Some(10)));
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(1), 1));
+		// the billing queues are full for the maximum delay after the next period
+		let full = vec![(9, 9); MAX_BILLINGS_PER_BLOCK as usize];
+		for block in 21..21 + MAX_BILLING_DELAY as u64 {
+			BillingQueue::<Test>::insert(block, BoundedVec::truncate_from(full.clone()));
+		}
+		run_to_block(11);
+		assert_eq!(last_event(), Event::MarketPlaceSubscriptionNotRescheduled(1, 1).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 100);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 100);
+		assert!(!Subscriptions::<Test>::contains_key(1, 1));
+		assert_eq!(SubscriberCount::<Test>::get(1), 0);
+	});
+}
+
+// test the subscriptions of a product removed are not charged to the new owner of the uid
+#[test]
+fn test_subscriptions_destroyed_product() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		assert_ok!(MarketPlace::set_subscription_plan(RuntimeOrigin::signed(2), 1, Some(10)));
+		assert_ok!(MarketPlace::subscribe(RuntimeOrigin::signed(1), 1));
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert!(!SubscriptionPlans::<Test>::contains_key(1));
+		assert!(!Subscriptions::<Test>::contains_key(1, 1));
+		assert_eq!(SubscriberCount::<Test>::get(1), 0);
+		// another seller creates a product with the same uid
+		setup_product(3, 1, 100);
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 100);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 100);
+		// a subscription to the previous owner lapses without charges
+		Subscriptions::<Test>::insert(
+			1,
+			1,
+			Subscription { seller: 2, amount: 100, period: 10, nextbilling: 21 },
+		);
+		BillingQueue::<Test>::insert(21, BoundedVec::truncate_from(vec![(1, 1)]));
+		run_to_block(21);
+		assert_eq!(last_event(), Event::MarketPlaceSubscriptionLapsed(1, 1).into());
+		assert_eq!(Balances::free_balance(1), 1_000_000 - 100);
+		assert_eq!(Balances::free_balance(2), 1_000_000 + 100);
+	});
+}
+
+// test the payment of a product priced in a currency mapped to an asset
+#[test]
+fn test_buy_now() {
+	new_test_ext().execute_with(|| {
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		assert_ok!(MarketPlace::create_currency(
+			RuntimeOrigin::root(),
+			b"USDC".to_vec(),
+			currency_info(b"USD Coin")
+		));
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product_info(b"USDC", None, 100)
+		));
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(2), 1));
+		// the currency is not settled from an asset yet
+		assert_noop!(
+			MarketPlace::buy_now(RuntimeOrigin::signed(3), 1, 3),
+			Error::<Test>::CurrencyAssetNotFound
+		);
+		// create the asset and fund the buyer
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 7, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 7, 3, 1_000));
+		// only a curator of the currencies can map the asset
+		assert_noop!(
+			MarketPlace::set_currency_asset(RuntimeOrigin::signed(1), b"USDC".to_vec(), Some(7)),
+			Error::<Test>::SignerIsNotCurator
+		);
+		assert_noop!(
+			MarketPlace::set_currency_asset(RuntimeOrigin::root(), b"EURC".to_vec(), Some(7)),
+			Error::<Test>::CurrencyCodeNotFound
+		);
+		assert_ok!(MarketPlace::set_currency_asset(
+			RuntimeOrigin::root(),
+			b"USDC".to_vec(),
+			Some(7)
+		));
+		assert_eq!(
+			last_event(),
+			Event::MarketPlaceCurrencyAssetSet(b"USDC".to_vec(), Some(7)).into()
+		);
+		// the seller cannot buy its own product
+		assert_noop!(
+			MarketPlace::buy_now(RuntimeOrigin::signed(2), 1, 1),
+			Error::<Test>::BuyerCannotBeSeller
+		);
+		// the total amount is paid in the asset and the receipt is issued
+		assert_ok!(MarketPlace::buy_now(RuntimeOrigin::signed(3), 1, 3));
+		assert_eq!(last_event(), Event::MarketPlaceProductBought(1, 3, 2, 3, 300).into());
+		assert_eq!(Assets::balance(7, 3), 700);
+		assert_eq!(Assets::balance(7, 2), 300);
+		assert_eq!(Balances::free_balance(3), 1_000_000);
+		assert_eq!(PurchaseReceipts::<Test>::get(1, 3), Some(2));
+		// the buyer cannot pay more than its balance of the asset
+		assert!(MarketPlace::buy_now(RuntimeOrigin::signed(3), 1, 7).is_err());
+		// the currency of a product cannot be destroyed, once unused its asset mapping is removed
+		assert_noop!(
+			MarketPlace::destroy_currency(RuntimeOrigin::root(), b"USDC".to_vec()),
+			Error::<Test>::InUse
+		);
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_ok!(MarketPlace::destroy_currency(RuntimeOrigin::root(), b"USDC".to_vec()));
+		let code: CurrencyCode = b"USDC".to_vec().try_into().unwrap();
+		assert_eq!(CurrencyAssets::<Test>::get(code), None);
+	});
+}
+
+// test the tax rates of the countries and categories and the tax quotes
+#[test]
+fn test_tax_rates() {
+	new_test_ext().execute_with(|| {
+		let root = RuntimeOrigin::root;
+		assert_ok!(MarketPlace::create_iso_country(
+			root(),
+			b"NL".to_vec(),
+			b"Netherlands".to_vec()
+		));
+		assert_ok!(MarketPlace::create_product_department(root(), 1, b"Books".to_vec()));
+		assert_ok!(MarketPlace::create_product_category(root(), 1, 1, b"Novels".to_vec()));
+		Products::<Test>::insert(1, product_info(b"USDC", Some((1, 1)), 1_000));
+		Products::<Test>::insert(2, product_info(b"USDC", None, 1_000));
+		// only the tax origin can set the rates
+		assert_noop!(
+			MarketPlace::set_tax_rate(
+				RuntimeOrigin::signed(1),
+				b"NL".to_vec(),
+				None,
+				Some(Permill::from_percent(21))
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			MarketPlace::set_tax_rate(
+				root(),
+				b"DE".to_vec(),
+				None,
+				Some(Permill::from_percent(19))
+			),
+			Error::<Test>::CountryCodeNotFound
+		);
+		assert_noop!(
+			MarketPlace::set_tax_rate(
+				root(),
+				b"NL".to_vec(),
+				Some((1, 2)),
+				Some(Permill::from_percent(9))
+			),
+			Error::<Test>::ProductCategoryNotFound
+		);
+		// without rates the tax is zero
+		assert_eq!(
+			MarketPlace::quote_tax(1, b"NL".to_vec()),
+			Some(TaxQuote { price: 1_000, rate: Permill::zero(), tax: 0, total: 1_000 })
+		);
+		assert_eq!(MarketPlace::quote_tax(3, b"NL".to_vec()), None);
+		// the rate of the category takes precedence over the rate of the country
+		assert_ok!(MarketPlace::set_tax_rate(
+			root(),
+			b"NL".to_vec(),
+			None,
+			Some(Permill::from_percent(21))
+		));
+		assert_eq!(
+			last_event(),
+			Event::MarketPlaceTaxRateSet(b"NL".to_vec(), None, Some(Permill::from_percent(21)))
+				.into()
+		);
+		assert_ok!(MarketPlace::set_tax_rate(
+			root(),
+			b"NL".to_vec(),
+			Some((1, 1)),
+			Some(Permill::from_percent(9))
+		));
+		assert_eq!(
+			MarketPlace::quote_tax(1, b"NL".to_vec()),
+			Some(TaxQuote { price: 1_000, rate: Permill::from_percent(9), tax: 90, total: 1_090 })
+		);
+		assert_eq!(
+			MarketPlace::quote_tax(2, b"NL".to_vec()),
+			Some(TaxQuote {
+				price: 1_000,
+				rate: Permill::from_percent(21),
+				tax: 210,
+				total: 1_210
+			})
+		);
+		// the country and the category are in use from the rates
+		let code: CountryCode = b"NL".to_vec().try_into().unwrap();
+		assert_eq!(ReferenceCount::<Test>::get(ReferenceKey::Country(code)), 2);
+		assert_noop!(MarketPlace::destroy_product_category(root(), 1, 1), Error::<Test>::InUse);
+		// removing the rates releases the references
+		assert_ok!(MarketPlace::set_tax_rate(root(), b"NL".to_vec(), Some((1, 1)), None));
+		assert_noop!(
+			MarketPlace::set_tax_rate(root(), b"NL".to_vec(), Some((1, 1)), None),
+			Error::<Test>::TaxRateNotFound
+		);
+		assert_ok!(MarketPlace::set_tax_rate(root(), b"NL".to_vec(), None, None));
+		assert_ok!(MarketPlace::destroy_product_category(root(), 1, 1));
+		assert_ok!(MarketPlace::destroy_iso_country(root(), b"NL".to_vec()));
+	});
+}
+
+// test the invoices anchored as documents of pallet-docsig
+#[test]
+fn test_invoices() {
+	new_test_ext().execute_with(|| {
+		setup_product(2, 1, 100);
+		Products::<Test>::insert(2, product_info(b"USDC", None, 50));
+		ProductOwner::<Test>::insert(2, 2);
+		Products::<Test>::insert(3, product_info(b"EURC", None, 80));
+		ProductOwner::<Test>::insert(3, 2);
+		Products::<Test>::insert(4, product_info(b"USDC", None, 10));
+		ProductOwner::<Test>::insert(4, 3);
+		// the invoice is issued from the owner of the products in a single currency
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(2), 1, 1, vec![]),
+			Error::<Test>::InvoiceLinesCannotBeEmpty
+		);
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(3), 1, 1, vec![(4, 1)]),
+			Error::<Test>::SellerDataNotFound
+		);
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(2), 1, 1, vec![(1, 1), (4, 1)]),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(2), 1, 1, vec![(1, 1), (3, 1)]),
+			Error::<Test>::InvoiceCurrencyMismatch
+		);
+		assert_ok!(MarketPlace::issue_invoice(
+			RuntimeOrigin::signed(2),
+			1,
+			1,
+			vec![(1, 2), (2, 3)]
+		));
+		let invoice = Invoices::<Test>::get(1).unwrap();
+		assert_eq!(invoice.seller, 2);
+		assert_eq!(invoice.buyer, 1);
+		assert_eq!(invoice.sellername.to_vec(), b"Seller Ltd".to_vec());
+		assert_eq!(invoice.lines.len(), 2);
+		assert_eq!(invoice.lines[1].amount, 150);
+		assert_eq!(invoice.total, 350);
+		assert_eq!(invoice.currency.to_vec(), b"USDC".to_vec());
+		assert_eq!(invoice.issuedat, 1);
+		// the hash of the invoice is anchored as document of the seller
+		let invoicehash = BlakeTwo256::hash_of(&invoice);
+		assert_eq!(last_event(), Event::MarketPlaceInvoiceIssued(1, 2, 1, invoicehash).into());
+		assert_eq!(DocSig::get_document(2, 1), invoicehash.as_ref().to_vec());
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(2), 1, 1, vec![(1, 1)]),
+			Error::<Test>::InvoiceAlreadyPresent
+		);
+		// the buyer can co-sign the invoice
+		assert_ok!(DocSig::sign_document(
+			RuntimeOrigin::signed(1),
+			1,
+			invoicehash.as_ref().to_vec()
+		));
+		assert_eq!(DocSig::get_signature(1, 1), invoicehash.as_ref().to_vec());
+		// the anchor fails when the seller has already a document with the same id
+		assert_ok!(DocSig::new_document(RuntimeOrigin::signed(2), 2, vec![1; 32]));
+		assert_noop!(
+			MarketPlace::issue_invoice(RuntimeOrigin::signed(2), 2, 1, vec![(1, 1)]),
+			pallet_docsig::Error::<Test>::DocumentAlreadyPresent
+		);
+	});
+}
+
+// test the parsing of the GPS coordinates and the matching of the areas
+#[test]
+fn test_geo() {
+	assert_eq!(geo::parse_coordinate(b"45.464211"), Some(45_464_211));
+	assert_eq!(geo::parse_coordinate(b"-9.19"), Some(-9_190_000));
+	assert_eq!(geo::parse_coordinate(b"+180"), Some(180_000_000));
+	assert_eq!(geo::parse_coordinate(b"0.12345678"), Some(123_456));
+	assert_eq!(geo::parse_coordinate(b""), None);
+	assert_eq!(geo::parse_coordinate(b"-."), None);
+	assert_eq!(geo::parse_coordinate(b"1000.0"), None);
+	assert_eq!(geo::parse_coordinate(b"45,46"), None);
+	assert!(geo::valid_latitude(-90_000_000) && !geo::valid_latitude(90_000_001));
+	assert!(geo::valid_longitude(180_000_000) && !geo::valid_longitude(-180_000_001));
+	// the haversine is 0 for the same point and 1 for antipodal points
+	assert_eq!(geo::haversine(45_000_000, 9_000_000, 45_000_000, 9_000_000), 0);
+	let antipodal = geo::haversine(0, 0, 0, 180_000_000);
+	assert!((antipodal - 1_000_000_000_000).abs() < 10);
+	// area of 10 km around Milan
+	let area = Area::from_text(b"45.464211", b"9.191383", b"45.554211", b"9.191383").unwrap();
+	// Sesto San Giovanni is about 8 km away, Monza 15 km, Rome 480 km
+	assert!(area.contains(45_533_900, 9_229_700));
+	assert!(!area.contains(45_584_500, 9_274_400));
+	assert!(!area.contains(41_902_782, 12_496_366));
+	// the area crosses the antimeridian
+	let area = Area::from_text(b"0", b"179.99", b"0", b"-179.99").unwrap();
+	assert!(area.contains(0, 180_000_000));
+	assert!(!area.contains(0, 179_900_000));
+	assert_eq!(Area::from_text(b"91", b"0", b"0", b"0"), None);
+}
+
+// test the validation of the shipping areas and the delivery to the GPS coordinates
+#[test]
+fn test_ships_to() {
+	new_test_ext().execute_with(|| {
+		IsoCountries::<Test>::insert(bounded::<_>(b"LR"), bounded::<_>(b"Liberia"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		let milan = Area::from_text(b"45.464211", b"9.191383", b"45.554211", b"9.191383").unwrap();
+		let mut info = seller_info(b"Seller Ltd");
+		info.shipmentarea =
+			vec![Area { centerlatitude: 90_000_001, ..milan.clone() }].try_into().unwrap();
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(2), None, info.clone()),
+			Error::<Test>::ShipmentAreaCenterLatitudeIsWrong
+		);
+		info.shipmentarea = vec![Area { borderlongitude: -180_000_001, ..milan.clone() }]
+			.try_into()
+			.unwrap();
+		assert_noop!(
+			MarketPlace::create_update_seller(RuntimeOrigin::signed(2), None, info.clone()),
+			Error::<Test>::ShipmentAreaBorderLongitudeIsWrong
+		);
+		info.shipmentarea = vec![milan.clone()].try_into().unwrap();
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(2), None, info));
+		assert!(MarketPlace::seller_ships_to(2, 45_533_900, 9_229_700));
+		assert!(!MarketPlace::seller_ships_to(2, 41_902_782, 12_496_366));
+		assert!(!MarketPlace::seller_ships_to(2, 95_000_000, 9_229_700));
+		assert!(!MarketPlace::seller_ships_to(3, 45_533_900, 9_229_700));
+		// the product without areas is delivered in the areas of the seller
+		Products::<Test>::insert(1, product_info(b"USDC", None, 100));
+		ProductOwner::<Test>::insert(1, 2);
+		assert!(MarketPlace::product_ships_to(1, 45_533_900, 9_229_700));
+		// the areas of the product take precedence
+		let mut product = product_info(b"USDC", None, 100);
+		product.shippingarea =
+			vec![Area::from_text(b"41.902782", b"12.496366", b"42", b"12.496366").unwrap()]
+				.try_into()
+				.unwrap();
+		Products::<Test>::insert(1, product);
+		assert!(!MarketPlace::product_ships_to(1, 45_533_900, 9_229_700));
+		assert!(MarketPlace::product_ships_to(1, 41_902_782, 12_496_366));
+		assert!(!MarketPlace::product_ships_to(2, 41_902_782, 12_496_366));
+	});
+}
+
+// test the migration of the GPS coordinates from text to microdegrees
+#[test]
+fn test_migration_v3() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<MarketPlace>();
+		let oldarea = |lat: &[u8], long: &[u8]| migrations::v3::old::Area {
+			centerlatitude: bounded(lat),
+			centerlongitude: bounded(long),
+			borderlatitude: bounded(b"45.554211"),
+			borderlongitude: bounded(long),
+		};
+		// the old encoding is the same without areas
+		let mut seller = migrations::v3::old::SellerInfo::<u64>::decode(
+			&mut &seller_info(b"Seller Ltd").encode()[..],
+		)
+		.unwrap();
+		seller.shipmentarea = BoundedVec::truncate_from(vec![
+			oldarea(b"45.464211", b"9.191383"),
+			oldarea(b"north", b"9.191383"),
+		]);
+		unhashed::put(&Sellers::<Test>::hashed_key_for(1), &seller);
+		let mut product = migrations::v3::old::ProductInfo::decode(
+			&mut &product_info(b"USDC", None, 100).encode()[..],
+		)
+		.unwrap();
+		product.shippingarea = BoundedVec::truncate_from(vec![oldarea(b"45.464211", b"9.191383")]);
+		unhashed::put(&Products::<Test>::hashed_key_for(1), &product);
+		// a record already in the new encoding is kept
+		let milan = Area::from_text(b"45.464211", b"9.191383", b"45.554211", b"9.191383").unwrap();
+		let mut newproduct = product_info(b"USDC", None, 200);
+		newproduct.shippingarea = vec![milan.clone()].try_into().unwrap();
+		Products::<Test>::insert(2, newproduct.clone());
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<MarketPlace>(), StorageVersion::new(3));
+		// the area with wrong coordinates is removed
+		let seller = Sellers::<Test>::get(1).unwrap();
+		assert_eq!(seller.shipmentarea.to_vec(), vec![milan.clone()]);
+		assert_eq!(seller.name.to_vec(), b"Seller Ltd".to_vec());
+		assert_eq!(Products::<Test>::get(1).unwrap().shippingarea.to_vec(), vec![milan]);
+		assert_eq!(Products::<Test>::get(2), Some(newproduct));
+	});
+}
+
+// test the validation of the GTIN check digit
+#[test]
+fn test_gtin() {
+	assert_eq!(aisland_normalize_gtin(b"96385074"), Some(*b"00000096385074"));
+	assert_eq!(aisland_normalize_gtin(b"012345678905"), Some(*b"00012345678905"));
+	assert_eq!(aisland_normalize_gtin(b"4006381333931"), Some(*b"04006381333931"));
+	assert_eq!(aisland_normalize_gtin(b"10012345678902"), Some(*b"10012345678902"));
+	// the same item in the UPC-A and EAN-13 formats has the same GTIN
+	assert_eq!(aisland_normalize_gtin(b"0012345678905"), aisland_normalize_gtin(b"012345678905"));
+	// wrong check digit, length or characters
+	assert_eq!(aisland_normalize_gtin(b"012345678906"), None);
+	assert_eq!(aisland_normalize_gtin(b"4006381333932"), None);
+	assert_eq!(aisland_normalize_gtin(b"12345678905"), None);
+	assert_eq!(aisland_normalize_gtin(b"01234567890a"), None);
+}
+
+// test the uniqueness of the GTIN of the products and the override for the resellers
+#[test]
+fn test_products_by_gtin() {
+	new_test_ext().execute_with(|| {
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Sellers::<Test>::insert(3, seller_info(b"Reseller Ltd"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		let product =
+			|upc: &[u8]| ProductInfo { upc: bounded(upc), ..product_info(b"USDC", None, 100) };
+		assert_noop!(
+			MarketPlace::create_update_product(
+				RuntimeOrigin::signed(2),
+				1,
+				product(b"012345678906")
+			),
+			Error::<Test>::UniversalProductCodeIsWrong
+		);
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product(b"012345678905")
+		));
+		assert_eq!(GtinOwner::<Test>::get(*b"00012345678905"), Some(1));
+		// the product can be updated keeping its code
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product(b"0012345678905")
+		));
+		// another product cannot register the same code, also in another format
+		assert_noop!(
+			MarketPlace::create_update_product(
+				RuntimeOrigin::signed(3),
+				2,
+				product(b"012345678905")
+			),
+			Error::<Test>::GtinAlreadyRegistered
+		);
+		assert_noop!(
+			MarketPlace::create_update_product(
+				RuntimeOrigin::signed(2),
+				2,
+				product(b"00012345678905")
+			),
+			Error::<Test>::GtinAlreadyRegistered
+		);
+		// a reseller can register the code
+		assert_noop!(
+			MarketPlace::set_reseller(RuntimeOrigin::signed(3), 3, true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(MarketPlace::set_reseller(RuntimeOrigin::root(), 3, true));
+		assert_eq!(last_event(), Event::MarketPlaceResellerSet(3, true).into());
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(3),
+			2,
+			product(b"012345678905")
+		));
+		assert!(ProductsByGtin::<Test>::contains_key(*b"00012345678905", 2));
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(2), 1));
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(3), 2));
+		assert_eq!(MarketPlace::query_products_by_gtin(b"012345678905".to_vec()), vec![1, 2]);
+		assert_eq!(
+			MarketPlace::query_products_by_gtin(b"012345678906".to_vec()),
+			Vec::<u32>::new()
+		);
+		// the products of the resellers don't block the code for the other sellers
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(MarketPlace::query_products_by_gtin(b"012345678905".to_vec()), vec![2]);
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			3,
+			product(b"012345678905")
+		));
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(2), 3));
+		// the index is built from the migration for the products stored
+		GtinOwner::<Test>::remove(*b"00012345678905");
+		ProductsByGtin::<Test>::remove(*b"00012345678905", 2);
+		StorageVersion::new(3).put::<MarketPlace>();
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<MarketPlace>(), StorageVersion::new(4));
+		let mut products = MarketPlace::query_products_by_gtin(b"012345678905".to_vec());
+		products.sort();
+		assert_eq!(products, vec![2, 3]);
+		assert_eq!(GtinOwner::<Test>::get(*b"00012345678905"), Some(3));
+	});
+}
+
+// test the verification of the sellers from the verifiers and its expiry
+#[test]
+fn test_seller_verification() {
+	new_test_ext().execute_with(|| {
+		IsoCountries::<Test>::insert(bounded::<_>(b"LR"), bounded::<_>(b"Liberia"));
+		ProductCategories::<Test>::insert(1, 1, bounded(b"Electronics"));
+		let certification = |description: &[u8]| Certification {
+			description: bounded(description),
+			verificationurl: bounded(b"https://registry.example.com/certificates/1"),
+		};
+		let mut info = seller_info(b"Seller Ltd");
+		info.certifications = vec![certification(b"ISO 9001"), certification(b"ISO 14001")]
+			.try_into()
+			.unwrap();
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info.clone()));
+		// only the verifiers can verify the sellers
+		assert_noop!(
+			MarketPlace::attest_certification(
+				RuntimeOrigin::signed(3),
+				1,
+				0,
+				AttestationStatus::Attested,
+				100
+			),
+			Error::<Test>::SignerIsNotVerifier
+		);
+		assert_ok!(MarketPlace::add_curator(RuntimeOrigin::root(), 3, CuratorDomain::Verification));
+		assert_noop!(
+			MarketPlace::attest_certification(
+				RuntimeOrigin::signed(3),
+				1,
+				2,
+				AttestationStatus::Attested,
+				100
+			),
+			Error::<Test>::CertificationNotFound
+		);
+		assert_noop!(
+			MarketPlace::attest_certification(
+				RuntimeOrigin::signed(3),
+				1,
+				0,
+				AttestationStatus::Attested,
+				1
+			),
+			Error::<Test>::ExpiryIsWrong
+		);
+		assert_ok!(MarketPlace::attest_certification(
+			RuntimeOrigin::signed(3),
+			1,
+			0,
+			AttestationStatus::Attested,
+			100
+		));
+		assert_eq!(
+			last_event(),
+			Event::MarketPlaceAttestationSet(1, 0, AttestationStatus::Attested, 100).into()
+		);
+		assert_noop!(
+			MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 4, 50),
+			Error::<Test>::KycLevelIsWrong
+		);
+		assert_noop!(
+			MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 2, 1, 50),
+			Error::<Test>::SellerDataNotFound
+		);
+		assert_ok!(MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 2, 50));
+		assert_eq!(last_event(), Event::MarketPlaceSellerKycSet(1, 2, 50).into());
+		assert_eq!(
+			MarketPlace::query_seller_verification(1),
+			Some(SellerVerification {
+				verified: true,
+				kyclevel: 2,
+				attested: vec![0],
+				rejected: vec![]
+			})
+		);
+		assert_eq!(MarketPlace::query_seller_verification(2), None);
+		// a rejected certification removes the badge
+		assert_ok!(MarketPlace::attest_certification(
+			RuntimeOrigin::signed(3),
+			1,
+			1,
+			AttestationStatus::Rejected,
+			100
+		));
+		assert_eq!(
+			MarketPlace::query_seller_verification(1),
+			Some(SellerVerification {
+				verified: false,
+				kyclevel: 2,
+				attested: vec![0],
+				rejected: vec![1]
+			})
+		);
+		// the changes of the seller data remove the verifications of the data changed
+		info.certifications = vec![certification(b"ISO 9001"), certification(b"ISO 45001")]
+			.try_into()
+			.unwrap();
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info.clone()));
+		assert!(CertificationAttestations::<Test>::contains_key(1, 0));
+		assert!(!CertificationAttestations::<Test>::contains_key(1, 1));
+		assert!(SellerKyc::<Test>::contains_key(1));
+		info.name = bounded(b"Seller Holding Ltd");
+		assert_ok!(MarketPlace::create_update_seller(RuntimeOrigin::signed(1), None, info));
+		assert!(!SellerKyc::<Test>::contains_key(1));
+		// the verifications expire
+		assert_ok!(MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 1, 50));
+		run_to_block(51);
+		assert_eq!(
+			MarketPlace::query_seller_verification(1),
+			Some(SellerVerification {
+				verified: false,
+				kyclevel: 0,
+				attested: vec![0],
+				rejected: vec![]
+			})
+		);
+		run_to_block(101);
+		assert_eq!(MarketPlace::query_seller_verification(1).unwrap().attested, Vec::<u32>::new());
+		// the level 0 removes the KYC and the removal of the seller removes its verifications
+		assert_ok!(MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 3, 200));
+		assert_ok!(MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 0, 0));
+		assert!(!SellerKyc::<Test>::contains_key(1));
+		assert_ok!(MarketPlace::set_seller_kyc(RuntimeOrigin::signed(3), 1, 3, 200));
+		assert_ok!(MarketPlace::destroy_seller(RuntimeOrigin::signed(1)));
+		assert!(!SellerKyc::<Test>::contains_key(1));
+		assert_eq!(CertificationAttestations::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+// test the lifecycle of the products and the catalogue of the active products
+#[test]
+fn test_product_status() {
+	new_test_ext().execute_with(|| {
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		let all = ProductFilter { seller: None, category: None, currency: None };
+		// the new products are drafts, not listed and not purchasable
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product_info(b"USDC", None, 100)
+		));
+		assert_eq!(MarketPlace::query_product_status(1), Some(ProductStatus::Draft));
+		assert_eq!(MarketPlace::query_products(all.clone(), 0, 10), vec![]);
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 1),
+			Error::<Test>::ProductIsNotActive
+		);
+		assert_noop!(
+			MarketPlace::pause_product(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ProductStatusIsWrong
+		);
+		// only the seller can publish the product
+		assert_noop!(
+			MarketPlace::publish_product(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SignerIsNotAuthorized
+		);
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceProductPublished(1).into());
+		assert_eq!(MarketPlace::query_products(all.clone(), 0, 10).len(), 1);
+		assert_ok!(MarketPlace::place_order(RuntimeOrigin::signed(1), 1, 1, 1));
+		// the update of the product keeps its status
+		assert_ok!(MarketPlace::create_update_product(
+			RuntimeOrigin::signed(2),
+			1,
+			product_info(b"USDC", None, 200)
+		));
+		assert_eq!(MarketPlace::query_product_status(1), Some(ProductStatus::Active));
+		// the paused product is pulled from the catalogue till it's published again
+		assert_ok!(MarketPlace::pause_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceProductPaused(1).into());
+		assert_eq!(MarketPlace::query_products(all.clone(), 0, 10), vec![]);
+		assert_noop!(
+			MarketPlace::place_order(RuntimeOrigin::signed(1), 2, 1, 1),
+			Error::<Test>::ProductIsNotActive
+		);
+		assert_ok!(MarketPlace::publish_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(MarketPlace::query_products(all.clone(), 0, 10).len(), 1);
+		// the discontinued product cannot be published anymore
+		assert_ok!(MarketPlace::discontinue_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(last_event(), Event::MarketPlaceProductDiscontinued(1).into());
+		assert_noop!(
+			MarketPlace::publish_product(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::ProductStatusIsWrong
+		);
+		assert_eq!(MarketPlace::query_products(all.clone(), 0, 10), vec![]);
+		// the products stored before the statuses are active
+		setup_product(2, 2, 100);
+		assert_eq!(MarketPlace::query_product_status(2), Some(ProductStatus::Active));
+		assert_eq!(MarketPlace::query_products(all, 0, 10).len(), 1);
+		// the removal of the product removes its status
+		assert_ok!(MarketPlace::destroy_product(RuntimeOrigin::signed(2), 1));
+		assert_eq!(MarketPlace::query_product_status(1), None);
+	});
+}
+
+// test the batch of products stored without reverting the products refused
+#[test]
+fn test_batch_upsert_products() {
+	new_test_ext().execute_with(|| {
+		Sellers::<Test>::insert(2, seller_info(b"Seller Ltd"));
+		Currencies::<Test>::insert(bounded::<_>(b"USDC"), currency_info(b"USD Coin"));
+		let product =
+			|upc: &[u8]| ProductInfo { upc: bounded(upc), ..product_info(b"USDC", None, 100) };
+		assert_noop!(
+			MarketPlace::batch_upsert_products(RuntimeOrigin::signed(2), BoundedVec::default()),
+			Error::<Test>::ProductsBatchCannotBeEmpty
+		);
+		// the second product has a wrong description and the fourth the GTIN of the first
+		let mut wrong = product(b"012345678929");
+		wrong.description = bounded(b"Short");
+		let info = MarketPlace::batch_upsert_products(
+			RuntimeOrigin::signed(2),
+			vec![
+				(1, product(b"012345678912")),
+				(2, wrong),
+				(3, product(b"012345678936")),
+				(4, product(b"012345678912")),
+			]
+			.try_into()
+			.unwrap(),
+		)
+		.unwrap();
+		// the weight counts the references of the products, the new ones have no previous data
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::batch_upsert_products(4, 4, 0)));
+		let events: Vec<RuntimeEvent> = System::events().into_iter().map(|r| r.event).collect();
+		assert!(
+			events.contains(&Event::MarketPlaceProductUpdated(1, product(b"012345678912")).into())
+		);
+		assert!(events.contains(
+			&Event::MarketPlaceProductRefused(2, Error::<Test>::ProductDescriptionTooShort.into())
+				.into()
+		));
+		assert!(
+			events.contains(&Event::MarketPlaceProductUpdated(3, product(b"012345678936")).into())
+		);
+		assert!(events.contains(
+			&Event::MarketPlaceProductRefused(4, Error::<Test>::GtinAlreadyRegistered.into())
+				.into()
+		));
+		assert_eq!(last_event(), Event::MarketPlaceProductsBatchStored(2, 4, 2).into());
+		// only the products accepted are stored with their deposit and indexes
+		assert!(Products::<Test>::contains_key(1));
+		assert!(!Products::<Test>::contains_key(2));
+		assert!(Products::<Test>::contains_key(3));
+		assert!(!Products::<Test>::contains_key(4));
+		assert_eq!(ProductOwner::<Test>::get(4), None);
+		assert!(!ProductDeposits::<Test>::contains_key(4));
+		assert_eq!(ProductsBySeller::<Test>::iter_key_prefix(2).count(), 2);
+		assert_eq!(
+			Balances::reserved_balance(2),
+			ProductDeposits::<Test>::get(1) + ProductDeposits::<Test>::get(3)
+		);
+		// the products of other sellers are refused
+		Sellers::<Test>::insert(3, seller_info(b"Other Seller"));
+		assert_ok!(MarketPlace::batch_upsert_products(
+			RuntimeOrigin::signed(3),
+			vec![(1, product(b"012345678943"))].try_into().unwrap()
+		));
+		assert_eq!(last_event(), Event::MarketPlaceProductsBatchStored(3, 1, 0).into());
+		assert_eq!(ProductOwner::<Test>::get(1), Some(2));
+	});
+}
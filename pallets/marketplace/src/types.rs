@@ -0,0 +1,398 @@
+//! Typed records of the market place, the variable fields are bounded so the storage can declare
+//! its maximum size.
+
+use crate::{geo, Balance, Vec};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::ConstU32, sp_runtime::Permill, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+
+/// Iso country code (2 bytes)
+pub type CountryCode = BoundedVec<u8, ConstU32<2>>;
+/// Currency code (from 3 to 5 bytes)
+pub type CurrencyCode = BoundedVec<u8, ConstU32<5>>;
+/// Iso language code (2 bytes)
+pub type LanguageCode = BoundedVec<u8, ConstU32<2>>;
+/// Unit of measurement system: "ms" metric, "iu" imperial, "us" United States
+pub type UnitMeasurement = BoundedVec<u8, ConstU32<2>>;
+/// Short text like names, emails and short descriptions, maximum 64 bytes
+pub type ShortText = BoundedVec<u8, ConstU32<64>>;
+/// Text like addresses, urls and ipfs addresses, maximum 128 bytes
+pub type Text = BoundedVec<u8, ConstU32<128>>;
+/// Long text like the descriptions and specifications of the products, maximum 8192 bytes
+pub type LongText = BoundedVec<u8, ConstU32<8192>>;
+/// Encrypted data of the login, maximum 1024 bytes
+pub type EncryptedData = BoundedVec<u8, ConstU32<1024>>;
+/// Global trade item number of 14 digits, the GTIN-8, UPC-A (GTIN-12) and EAN-13 codes are padded
+/// with leading zeros
+pub type Gtin = [u8; 14];
+
+/// Reference data that a curator is allowed to maintain
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CuratorDomain {
+	/// iso countries and dial codes
+	Countries,
+	/// currencies
+	Currencies,
+	/// departments, categories, colors and sizes of the products
+	Categories,
+	/// shippers and shipping rates
+	Shipping,
+	/// manufacturers, brands and product models
+	Brands,
+	/// verification of the certifications and KYC level of the sellers
+	Verification,
+}
+
+/// Reference data used from the sellers, products, shippers and other reference data, the number
+/// of references is counted to refuse the removal of the data in use
+#[derive(
+	Encode, Decode, Clone, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum ReferenceKey {
+	Country(CountryCode),
+	Currency(CurrencyCode),
+	/// (department, category)
+	Category(u32, u32),
+	Color(u32),
+	Size(u32),
+	Shipper(u32),
+	Manufacturer(u32),
+	Brand(u32),
+}
+
+/// Type of seller
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SellerType {
+	Company,
+	Freelancer,
+	Individual,
+	GovernmentAgency,
+	Ngo,
+}
+
+/// Certification of a seller with the url to verify it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Certification {
+	pub description: ShortText,
+	pub verificationurl: ShortText,
+}
+
+/// Phone number with its description (for example "Customer support")
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Phone {
+	pub description: ShortText,
+	pub number: ShortText,
+}
+
+/// Country where a seller ships, with the default worldwide the countries can be excluded
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CountryRule {
+	pub country: CountryCode,
+	pub excluded: bool,
+}
+
+/// Delivery area delimited by GPS coordinates in microdegrees (degrees * 1_000_000), the first
+/// point is the center of a circle and the second point is on the border of the same circle
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Area {
+	pub centerlatitude: i32,
+	pub centerlongitude: i32,
+	pub borderlatitude: i32,
+	pub borderlongitude: i32,
+}
+
+impl Area {
+	/// Parse an area from the coordinates in decimal degrees, None when a coordinate is wrong
+	pub fn from_text(
+		centerlatitude: &[u8],
+		centerlongitude: &[u8],
+		borderlatitude: &[u8],
+		borderlongitude: &[u8],
+	) -> Option<Self> {
+		let area = Area {
+			centerlatitude: geo::parse_coordinate(centerlatitude)?,
+			centerlongitude: geo::parse_coordinate(centerlongitude)?,
+			borderlatitude: geo::parse_coordinate(borderlatitude)?,
+			borderlongitude: geo::parse_coordinate(borderlongitude)?,
+		};
+		area.is_valid().then_some(area)
+	}
+
+	/// Check the coordinates are in range
+	pub fn is_valid(&self) -> bool {
+		geo::valid_latitude(self.centerlatitude) &&
+			geo::valid_longitude(self.centerlongitude) &&
+			geo::valid_latitude(self.borderlatitude) &&
+			geo::valid_longitude(self.borderlongitude)
+	}
+
+	/// Check the point (microdegrees) is inside the circle of the area
+	pub fn contains(&self, latitude: i32, longitude: i32) -> bool {
+		let radius = geo::haversine(
+			self.centerlatitude,
+			self.centerlongitude,
+			self.borderlatitude,
+			self.borderlongitude,
+		);
+		geo::haversine(self.centerlatitude, self.centerlongitude, latitude, longitude) <= radius
+	}
+}
+
+/// Seller data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SellerInfo<AccountId> {
+	pub sellertype: SellerType,
+	/// company name or name/surname
+	pub name: ShortText,
+	pub address: Text,
+	pub zip: ShortText,
+	pub pobox: ShortText,
+	pub city: ShortText,
+	pub websites: BoundedVec<Text, ConstU32<8>>,
+	pub socialurls: BoundedVec<Text, ConstU32<8>>,
+	pub certifications: BoundedVec<Certification, ConstU32<16>>,
+	pub emailinfo: ShortText,
+	pub emailsupport: ShortText,
+	pub phones: BoundedVec<Phone, ConstU32<8>>,
+	/// (department, category) of the products/services sold
+	pub categories: BoundedVec<(u32, u32), ConstU32<32>>,
+	/// countries of shipment
+	pub countries: BoundedVec<CountryRule, ConstU32<256>>,
+	pub shipmentarea: BoundedVec<Area, ConstU32<8>>,
+	pub defaultlanguage: Option<LanguageCode>,
+	pub defaultunitmeasurement: Option<UnitMeasurement>,
+	/// default return policy in days
+	pub defaultreturnpolicy: Option<u32>,
+	/// account allowed to update the seller data (gasless transactions)
+	pub proxyaccount: Option<AccountId>,
+}
+
+impl<AccountId> SellerInfo<AccountId> {
+	/// Reference data used from the seller
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		self.categories
+			.iter()
+			.map(|(department, category)| ReferenceKey::Category(*department, *category))
+			.chain(self.countries.iter().map(|c| ReferenceKey::Country(c.country.clone())))
+			.collect()
+	}
+}
+
+/// Photo, video or document of a product
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Media {
+	pub description: ShortText,
+	pub filename: Text,
+	pub ipfs: Text,
+	/// color of the product shown
+	pub color: Option<u32>,
+}
+
+/// Dimension of a package of the product, the weight is in grams
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Dimension {
+	pub length: u32,
+	pub wide: u32,
+	pub height: u32,
+	pub weight: u32,
+}
+
+/// Lifecycle of a product, only the active products are listed in the catalogue and can be
+/// purchased
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ProductStatus {
+	/// prepared from the seller before the launch
+	Draft,
+	Active,
+	/// temporarily pulled from the catalogue
+	Paused,
+	/// permanently pulled from the catalogue
+	Discontinued,
+}
+
+/// Product data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProductInfo {
+	pub description: ShortText,
+	pub longdescription: LongText,
+	pub price: Balance,
+	pub currency: CurrencyCode,
+	/// optional (department, category) used to index the product
+	pub category: Option<(u32, u32)>,
+	pub specifications: LongText,
+	pub media: BoundedVec<Media, ConstU32<16>>,
+	pub colors: BoundedVec<u32, ConstU32<32>>,
+	pub sizes: BoundedVec<u32, ConstU32<32>>,
+	pub dimension: BoundedVec<Dimension, ConstU32<8>>,
+	/// universal product code
+	pub upc: ShortText,
+	pub shippingcountries: BoundedVec<CountryCode, ConstU32<256>>,
+	pub shippingarea: BoundedVec<Area, ConstU32<8>>,
+	pub shippers: BoundedVec<u32, ConstU32<16>>,
+	/// url of the api to check the availability
+	pub apiavailability: Option<Text>,
+	pub language: Option<LanguageCode>,
+}
+
+impl ProductInfo {
+	/// Reference data used from the product
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		let mut references = Vec::from([ReferenceKey::Currency(self.currency.clone())]);
+		if let Some((department, category)) = self.category {
+			references.push(ReferenceKey::Category(department, category));
+		}
+		references.extend(self.media.iter().filter_map(|m| m.color).map(ReferenceKey::Color));
+		references.extend(self.colors.iter().copied().map(ReferenceKey::Color));
+		references.extend(self.sizes.iter().copied().map(ReferenceKey::Size));
+		references.extend(self.shippingcountries.iter().cloned().map(ReferenceKey::Country));
+		references.extend(self.shippers.iter().copied().map(ReferenceKey::Shipper));
+		references
+	}
+}
+
+/// Category of a currency
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CurrencyCategory {
+	Crypto,
+	Fiat,
+}
+
+/// Currency data, the country is required for the fiat currencies and the blockchain for the
+/// crypto currencies
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CurrencyInfo {
+	pub name: ShortText,
+	pub category: CurrencyCategory,
+	pub country: Option<CountryCode>,
+	pub blockchain: Option<ShortText>,
+	/// address of the crypto currency (contract, asset id...)
+	pub address: Option<Text>,
+}
+
+impl CurrencyInfo {
+	/// Reference data used from the currency
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		self.country.iter().cloned().map(ReferenceKey::Country).collect()
+	}
+}
+
+/// Size of a product, for example code "XL", description "Extra Large", area "Europe"
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SizeInfo {
+	pub code: ShortText,
+	pub description: ShortText,
+	pub area: ShortText,
+}
+/// Table of sizes stored with a single uid
+pub type SizeTable = BoundedVec<SizeInfo, ConstU32<32>>;
+
+/// Manufacturer data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ManufacturerInfo {
+	pub name: ShortText,
+	pub website: ShortText,
+}
+
+/// Brand data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BrandInfo {
+	pub name: ShortText,
+	/// uid of the manufacturer
+	pub manufacturer: u32,
+}
+
+impl BrandInfo {
+	/// Reference data used from the brand
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		Vec::from([ReferenceKey::Manufacturer(self.manufacturer)])
+	}
+}
+
+/// Product model data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProductModelInfo {
+	pub name: ShortText,
+	/// uid of the brand
+	pub brand: u32,
+}
+
+impl ProductModelInfo {
+	/// Reference data used from the product model
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		Vec::from([ReferenceKey::Brand(self.brand)])
+	}
+}
+
+/// Shipper data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ShipperInfo {
+	pub name: ShortText,
+	pub website: ShortText,
+	pub origincountries: BoundedVec<CountryCode, ConstU32<256>>,
+	pub destinationcountries: BoundedVec<CountryCode, ConstU32<256>>,
+}
+
+impl ShipperInfo {
+	/// Reference data used from the shipper
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		self.origincountries
+			.iter()
+			.chain(self.destinationcountries.iter())
+			.cloned()
+			.map(ReferenceKey::Country)
+			.collect()
+	}
+}
+
+/// Rate to ship a package to the destination country, for a weight between fromkg and tokg
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ShippingRate {
+	pub destination: CountryCode,
+	pub fromkg: u32,
+	pub tokg: u32,
+	pub rate: Balance,
+}
+
+/// Shipping rates of a shipper from the origin country, in the currency set
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ShippingRateTable {
+	pub shipperid: u32,
+	pub origincountry: CountryCode,
+	pub currency: CurrencyCode,
+	pub rates: BoundedVec<ShippingRate, ConstU32<256>>,
+}
+
+impl ShippingRateTable {
+	/// Reference data used from the shipping rates
+	pub fn references(&self) -> Vec<ReferenceKey> {
+		let mut references = Vec::from([
+			ReferenceKey::Shipper(self.shipperid),
+			ReferenceKey::Country(self.origincountry.clone()),
+			ReferenceKey::Currency(self.currency.clone()),
+		]);
+		references.extend(self.rates.iter().map(|r| ReferenceKey::Country(r.destination.clone())));
+		references
+	}
+}
+
+/// Tax line of a product shipped to a destination country, the tax is added to the listed price
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TaxQuote {
+	pub price: Balance,
+	pub rate: Permill,
+	pub tax: Balance,
+	pub total: Balance,
+}
+
+/// Verification of a seller from the chain data, the KYC level is 0 when missing or expired and
+/// the attestations expired are not listed
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SellerVerification {
+	/// badge of the verified sellers: valid KYC level and no certification rejected
+	pub verified: bool,
+	pub kyclevel: u8,
+	/// indexes of the certifications attested
+	pub attested: Vec<u32>,
+	/// indexes of the certifications rejected
+	pub rejected: Vec<u32>,
+}
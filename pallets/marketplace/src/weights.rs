@@ -1,30 +1,13 @@
-
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_marketplace
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_template
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// pallets/template/src/weights.rs
-// --template
-// ../../.maintain/frame-weight-template.hbs
+//! THE WEIGHTS OF THE MARKETPLACE CALLS ARE ESTIMATES, THEY HAVE NOT BEEN BENCHMARKED.
+//! They are computed from the storage items each call reads and writes (listed above every
+//! function) with a rough execution time, the components scale them with the input of the calls.
+//! Replace this file with the output of the benchmark cli once the calls are benchmarked.
+//!
+//! Only `do_something` and `cause_error` are benchmark results, they were generated for
+//! pallet_template with the substrate benchmark cli version 4.0.0-dev on 2023-04-06 (steps 50,
+//! repeat 20, wasm compiled execution, chain dev) and are kept for the calls not estimated yet.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -33,13 +16,64 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_marketplace.
 pub trait WeightInfo {
 	fn do_something() -> Weight;
 	fn cause_error() -> Weight;
+	// ESTIMATES: the weights of the following calls are estimated from the storage they access,
+	// they have not been benchmarked
+	fn place_order() -> Weight;
+	fn confirm_shipment() -> Weight;
+	fn confirm_delivery() -> Weight;
+	fn cancel_order() -> Weight;
+	fn destroy_product(r: u32, v: u32, s: u32, ) -> Weight;
+	fn issue_receipt() -> Weight;
+	fn review_product() -> Weight;
+	fn set_stock() -> Weight;
+	fn adjust_stock() -> Weight;
+	fn record_sale() -> Weight;
+	fn open_escrow() -> Weight;
+	fn release_escrow() -> Weight;
+	fn open_dispute() -> Weight;
+	fn submit_evidence() -> Weight;
+	fn resolve_dispute() -> Weight;
+	fn add_curator() -> Weight;
+	fn remove_curator() -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn create_coupon(p: u32, ) -> Weight;
+	fn destroy_coupon(b: u32, ) -> Weight;
+	fn redeem_coupon() -> Weight;
+	fn set_subscription_plan() -> Weight;
+	fn subscribe() -> Weight;
+	fn unsubscribe() -> Weight;
+	fn set_currency_asset() -> Weight;
+	fn buy_now() -> Weight;
+	fn set_tax_rate() -> Weight;
+	fn issue_invoice(l: u32, ) -> Weight;
+	fn set_reseller() -> Weight;
+	fn attest_certification() -> Weight;
+	fn set_seller_kyc() -> Weight;
+	fn request_return() -> Weight;
+	fn approve_return() -> Weight;
+	fn reject_return() -> Weight;
+	fn release_payment() -> Weight;
+	fn publish_product() -> Weight;
+	fn pause_product() -> Weight;
+	fn discontinue_product() -> Weight;
+	fn assign_product_owner() -> Weight;
+	fn create_update_seller(n: u32, o: u32, ) -> Weight;
+	fn destroy_seller(o: u32, ) -> Weight;
+	fn create_update_product(n: u32, o: u32, ) -> Weight;
+	fn create_shipper(n: u32, ) -> Weight;
+	fn destroy_shipper(o: u32, ) -> Weight;
+	fn create_shipping_rates(n: u32, o: u32, ) -> Weight;
+	fn destroy_shipping_rates(o: u32, ) -> Weight;
+	fn batch_upsert_products(p: u32, n: u32, o: u32, ) -> Weight;
+	fn claim_return() -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_marketplace using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: TemplateModule Something (r:0 w:1)
@@ -63,6 +97,533 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	// ESTIMATES: the following weights have not been benchmarked
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn place_order() -> Weight {
+		Weight::from_parts(44_000_000, 34_093)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	fn confirm_shipment() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MarketPlace ReturnWindows (r:0 w:1)
+	fn confirm_delivery() -> Weight {
+		Weight::from_parts(38_000_000, 17_686)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(24_000_000, 7_093)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:1)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:1)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:1)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace SubscriptionPlans (r:0 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:0 w:1)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace Stock (r:0 w:1)
+	/// Storage: MarketPlace Subscriptions (r:0 w:1)
+	/// The range of component `r` is `[1, 354]`.
+	/// The range of component `v` is `[0, 1024]`.
+	/// The range of component `s` is `[0, 1000]`.
+	fn destroy_product(r: u32, v: u32, s: u32, ) -> Weight {
+		Weight::from_parts(89_000_000, 27_093)
+			.saturating_add(Weight::from_parts(3_000_000, 3_500).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn issue_receipt() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace PurchaseReceipts (r:1 w:0)
+	/// Storage: MarketPlace Reviews (r:1 w:1)
+	/// Storage: MarketPlace ProductRatings (r:1 w:1)
+	/// Storage: MarketPlace SellerRatings (r:2 w:2)
+	fn review_product() -> Weight {
+		Weight::from_parts(40_000_000, 17_500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:0 w:1)
+	fn set_stock() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:1 w:1)
+	fn adjust_stock() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:1 w:1)
+	fn record_sale() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn open_escrow() -> Weight {
+		Weight::from_parts(44_000_000, 34_293)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn release_escrow() -> Weight {
+		Weight::from_parts(28_000_000, 10_886)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	fn open_dispute() -> Weight {
+		Weight::from_parts(17_000_000, 3_700)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:0)
+	/// Storage: MarketPlace DisputeEvidences (r:1 w:1)
+	fn submit_evidence() -> Weight {
+		Weight::from_parts(22_000_000, 11_700)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(28_000_000, 10_886)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:1)
+	fn add_curator() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:1)
+	fn remove_curator() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Auctions (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace AuctionsEndingAt (r:1 w:1)
+	fn create_auction() -> Weight {
+		Weight::from_parts(44_000_000, 34_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Auctions (r:2 w:2)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:3 w:3)
+	fn bid() -> Weight {
+		Weight::from_parts(41_000_000, 24_779)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Coupons (r:1 w:1)
+	/// Storage: MarketPlace ProductCategories (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace NextCouponNonce (r:1 w:1)
+	/// Storage: MarketPlace CouponNonces (r:0 w:1)
+	/// The range of component `p` is `[0, 32]`.
+	fn create_coupon(p: u32, ) -> Weight {
+		Weight::from_parts(33_000_000, 16_300)
+			.saturating_add(Weight::from_parts(2_000_000, 3_500).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Coupons (r:1 w:1)
+	/// Storage: MarketPlace CouponNonces (r:1 w:1)
+	/// Storage: MarketPlace CouponRedemptions (r:0 w:1)
+	/// Storage: MarketPlace CouponBuyerRedemptions (r:0 w:1000)
+	/// The range of component `b` is `[0, 1000]`.
+	fn destroy_coupon(b: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 7_600)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Coupons (r:1 w:0)
+	/// Storage: MarketPlace CouponNonces (r:1 w:0)
+	/// Storage: MarketPlace CouponRedemptions (r:1 w:1)
+	/// Storage: MarketPlace CouponBuyerRedemptions (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn redeem_coupon() -> Weight {
+		Weight::from_parts(68_000_000, 48_786)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace SubscriptionPlans (r:0 w:1)
+	fn set_subscription_plan() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace SubscriptionPlans (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Subscriptions (r:1 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:1 w:1)
+	/// Storage: MarketPlace BillingQueue (r:100 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn subscribe() -> Weight {
+		Weight::from_parts(265_000_000, 394_686)
+			.saturating_add(T::DbWeight::get().reads(109_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Subscriptions (r:1 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:1 w:1)
+	fn unsubscribe() -> Weight {
+		Weight::from_parts(24_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace CurrencyAssets (r:0 w:1)
+	fn set_currency_asset() -> Weight {
+		Weight::from_parts(25_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace CurrencyAssets (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn buy_now() -> Weight {
+		Weight::from_parts(65_000_000, 45_118)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ProductCategories (r:1 w:0)
+	/// Storage: MarketPlace TaxRates (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:2 w:2)
+	fn set_tax_rate() -> Weight {
+		Weight::from_parts(38_000_000, 17_500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Invoices (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: DocSig Documents (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// The range of component `l` is `[1, 32]`.
+	fn issue_invoice(l: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 21_000)
+			.saturating_add(Weight::from_parts(4_000_000, 16_500).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Resellers (r:0 w:1)
+	fn set_reseller() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:1)
+	fn attest_certification() -> Weight {
+		Weight::from_parts(25_000_000, 10_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	fn set_seller_kyc() -> Weight {
+		Weight::from_parts(25_000_000, 10_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	fn request_return() -> Weight {
+		Weight::from_parts(22_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn approve_return() -> Weight {
+		Weight::from_parts(35_000_000, 14_186)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	fn reject_return() -> Weight {
+		Weight::from_parts(22_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn release_payment() -> Weight {
+		Weight::from_parts(29_000_000, 10_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn publish_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn pause_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn discontinue_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:1)
+	fn assign_product_owner() -> Weight {
+		Weight::from_parts(32_000_000, 23_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:1)
+	/// Storage: MarketPlace SellerDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:16)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 288]`.
+	/// The range of component `o` is `[0, 288]`.
+	fn create_update_seller(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 14_093)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(20_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:1)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:16)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	/// Storage: MarketPlace SellerDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 288]`.
+	fn destroy_seller(o: u32, ) -> Weight {
+		Weight::from_parts(74_000_000, 14_093)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(20_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace Resellers (r:1 w:0)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:2)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[1, 354]`.
+	/// The range of component `o` is `[0, 354]`.
+	fn create_update_product(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 37_593)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 512]`.
+	fn create_shipper(n: u32, ) -> Weight {
+		Weight::from_parts(23_000_000, 3_500)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 512]`.
+	fn destroy_shipper(o: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 7_000)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:0)
+	/// Storage: MarketPlace ShippingRates (r:1 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[3, 259]`.
+	/// The range of component `o` is `[0, 259]`.
+	fn create_shipping_rates(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(31_000_000, 7_000)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace ShippingRates (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 259]`.
+	fn destroy_shipping_rates(o: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 3_500)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace Resellers (r:1 w:0)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:2)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `p` is `[1, 100]`.
+	/// The range of component `n` is `[1, 35400]`.
+	/// The range of component `o` is `[0, 35400]`.
+	fn batch_upsert_products(p: u32, n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(Weight::from_parts(95_000_000, 37_593).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes((13_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim_return() -> Weight {
+		Weight::from_parts(35_000_000, 14_186)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -88,4 +649,531 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	// ESTIMATES: the following weights have not been benchmarked
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn place_order() -> Weight {
+		Weight::from_parts(44_000_000, 34_093)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	fn confirm_shipment() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MarketPlace ReturnWindows (r:0 w:1)
+	fn confirm_delivery() -> Weight {
+		Weight::from_parts(38_000_000, 17_686)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(24_000_000, 7_093)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:1)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:1)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:1)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace SubscriptionPlans (r:0 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:0 w:1)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace Stock (r:0 w:1)
+	/// Storage: MarketPlace Subscriptions (r:0 w:1)
+	/// The range of component `r` is `[1, 354]`.
+	/// The range of component `v` is `[0, 1024]`.
+	/// The range of component `s` is `[0, 1000]`.
+	fn destroy_product(r: u32, v: u32, s: u32, ) -> Weight {
+		Weight::from_parts(89_000_000, 27_093)
+			.saturating_add(Weight::from_parts(3_000_000, 3_500).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn issue_receipt() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace PurchaseReceipts (r:1 w:0)
+	/// Storage: MarketPlace Reviews (r:1 w:1)
+	/// Storage: MarketPlace ProductRatings (r:1 w:1)
+	/// Storage: MarketPlace SellerRatings (r:2 w:2)
+	fn review_product() -> Weight {
+		Weight::from_parts(40_000_000, 17_500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:0 w:1)
+	fn set_stock() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:1 w:1)
+	fn adjust_stock() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Stock (r:1 w:1)
+	fn record_sale() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn open_escrow() -> Weight {
+		Weight::from_parts(44_000_000, 34_293)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn release_escrow() -> Weight {
+		Weight::from_parts(28_000_000, 10_886)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	fn open_dispute() -> Weight {
+		Weight::from_parts(17_000_000, 3_700)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:0)
+	/// Storage: MarketPlace DisputeEvidences (r:1 w:1)
+	fn submit_evidence() -> Weight {
+		Weight::from_parts(22_000_000, 11_700)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Escrows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(28_000_000, 10_886)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:1)
+	fn add_curator() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:1)
+	fn remove_curator() -> Weight {
+		Weight::from_parts(17_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Auctions (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace AuctionsEndingAt (r:1 w:1)
+	fn create_auction() -> Weight {
+		Weight::from_parts(44_000_000, 34_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Auctions (r:2 w:2)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: System Account (r:3 w:3)
+	fn bid() -> Weight {
+		Weight::from_parts(41_000_000, 24_779)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Coupons (r:1 w:1)
+	/// Storage: MarketPlace ProductCategories (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace NextCouponNonce (r:1 w:1)
+	/// Storage: MarketPlace CouponNonces (r:0 w:1)
+	/// The range of component `p` is `[0, 32]`.
+	fn create_coupon(p: u32, ) -> Weight {
+		Weight::from_parts(33_000_000, 16_300)
+			.saturating_add(Weight::from_parts(2_000_000, 3_500).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Coupons (r:1 w:1)
+	/// Storage: MarketPlace CouponNonces (r:1 w:1)
+	/// Storage: MarketPlace CouponRedemptions (r:0 w:1)
+	/// Storage: MarketPlace CouponBuyerRedemptions (r:0 w:1000)
+	/// The range of component `b` is `[0, 1000]`.
+	fn destroy_coupon(b: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 7_600)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Coupons (r:1 w:0)
+	/// Storage: MarketPlace CouponNonces (r:1 w:0)
+	/// Storage: MarketPlace CouponRedemptions (r:1 w:1)
+	/// Storage: MarketPlace CouponBuyerRedemptions (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn redeem_coupon() -> Weight {
+		Weight::from_parts(68_000_000, 48_786)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace SubscriptionPlans (r:0 w:1)
+	fn set_subscription_plan() -> Weight {
+		Weight::from_parts(25_000_000, 16_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace SubscriptionPlans (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Subscriptions (r:1 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:1 w:1)
+	/// Storage: MarketPlace BillingQueue (r:100 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn subscribe() -> Weight {
+		Weight::from_parts(265_000_000, 394_686)
+			.saturating_add(RocksDbWeight::get().reads(109_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace Subscriptions (r:1 w:1)
+	/// Storage: MarketPlace SubscriberCount (r:1 w:1)
+	fn unsubscribe() -> Weight {
+		Weight::from_parts(24_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace CurrencyAssets (r:0 w:1)
+	fn set_currency_asset() -> Weight {
+		Weight::from_parts(25_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace CurrencyAssets (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace PurchaseReceipts (r:0 w:1)
+	fn buy_now() -> Weight {
+		Weight::from_parts(65_000_000, 45_118)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ProductCategories (r:1 w:0)
+	/// Storage: MarketPlace TaxRates (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:2 w:2)
+	fn set_tax_rate() -> Weight {
+		Weight::from_parts(38_000_000, 17_500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: MarketPlace Invoices (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: DocSig Documents (r:1 w:1)
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// The range of component `l` is `[1, 32]`.
+	fn issue_invoice(l: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 21_000)
+			.saturating_add(Weight::from_parts(4_000_000, 16_500).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Resellers (r:0 w:1)
+	fn set_reseller() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:1)
+	fn attest_certification() -> Weight {
+		Weight::from_parts(25_000_000, 10_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Curators (r:1 w:0)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	fn set_seller_kyc() -> Weight {
+		Weight::from_parts(25_000_000, 10_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	fn request_return() -> Weight {
+		Weight::from_parts(22_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn approve_return() -> Weight {
+		Weight::from_parts(35_000_000, 14_186)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	fn reject_return() -> Weight {
+		Weight::from_parts(22_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:0)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn release_payment() -> Weight {
+		Weight::from_parts(29_000_000, 10_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn publish_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn pause_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:0)
+	/// Storage: MarketPlace ProductStatuses (r:1 w:1)
+	fn discontinue_product() -> Weight {
+		Weight::from_parts(27_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MarketPlace Products (r:1 w:0)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:1)
+	fn assign_product_owner() -> Weight {
+		Weight::from_parts(32_000_000, 23_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:1)
+	/// Storage: MarketPlace SellerDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:16)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 288]`.
+	/// The range of component `o` is `[0, 288]`.
+	fn create_update_seller(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 14_093)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(20_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:1)
+	/// Storage: MarketPlace CertificationAttestations (r:0 w:16)
+	/// Storage: MarketPlace SellerKyc (r:0 w:1)
+	/// Storage: MarketPlace SellerDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 288]`.
+	fn destroy_seller(o: u32, ) -> Weight {
+		Weight::from_parts(74_000_000, 14_093)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(20_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace Resellers (r:1 w:0)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:2)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[1, 354]`.
+	/// The range of component `o` is `[0, 354]`.
+	fn create_update_product(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 37_593)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 512]`.
+	fn create_shipper(n: u32, ) -> Weight {
+		Weight::from_parts(23_000_000, 3_500)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 512]`.
+	fn destroy_shipper(o: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 7_000)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Shippers (r:1 w:0)
+	/// Storage: MarketPlace ShippingRates (r:1 w:1)
+	/// Storage: MarketPlace IsoCountries (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `n` is `[3, 259]`.
+	/// The range of component `o` is `[0, 259]`.
+	fn create_shipping_rates(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(31_000_000, 7_000)
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace ShippingRates (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `o` is `[0, 259]`.
+	fn destroy_shipping_rates(o: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 3_500)
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Sellers (r:1 w:0)
+	/// Storage: MarketPlace Products (r:1 w:1)
+	/// Storage: MarketPlace ProductOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductStatuses (r:0 w:1)
+	/// Storage: MarketPlace Resellers (r:1 w:0)
+	/// Storage: MarketPlace GtinOwner (r:1 w:1)
+	/// Storage: MarketPlace ProductsByGtin (r:0 w:1)
+	/// Storage: MarketPlace ProductsBySeller (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCategory (r:0 w:2)
+	/// Storage: MarketPlace ProductsByCurrency (r:0 w:2)
+	/// Storage: MarketPlace ProductDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MarketPlace Currencies (r:1 w:0)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// Storage: MarketPlace ReferenceCount (r:1 w:1)
+	/// The range of component `p` is `[1, 100]`.
+	/// The range of component `n` is `[1, 35400]`.
+	/// The range of component `o` is `[0, 35400]`.
+	fn batch_upsert_products(p: u32, n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(Weight::from_parts(95_000_000, 37_593).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 7_000).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 3_500).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes((13_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+	}
+	/// Storage: MarketPlace Orders (r:1 w:1)
+	/// Storage: MarketPlace ReturnWindows (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim_return() -> Weight {
+		Weight::from_parts(35_000_000, 14_186)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
@@ -51,6 +51,10 @@ pub use pallet_docsig;
 pub use pallet_marketplace;
 pub use pallet_vote;
 
+/// Constant values used within the runtime.
+pub mod constants;
+use constants::currency::{deposit, DOLLARS};
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -106,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 101 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 108,
+	spec_version: 142,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -252,6 +256,31 @@ impl pallet_balances::Config for Runtime {
 	type MaxHolds = ();
 }
 
+/// Configure the pallet-assets used to settle the non native currencies
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin =
+		frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<{ 100 * DOLLARS }>;
+	type AssetAccountDeposit = ConstU128<{ deposit(1, 16) }>;
+	type MetadataDepositBase = ConstU128<{ deposit(1, 68) }>;
+	type MetadataDepositPerByte = ConstU128<{ deposit(0, 1) }>;
+	type ApprovalDeposit = ConstU128<{ deposit(1, 0) }>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	pub FeeMultiplier: Multiplier = Multiplier::one();
 }
@@ -270,10 +299,47 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+parameter_types! {
+	pub MarketPlaceMaxInitializeWeight: Weight =
+		Perbill::from_percent(10) * BlockWeights::get().max_block;
+}
+
 /// Configure the pallet-marketplace in pallets/marketplace.
 impl pallet_marketplace::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_marketplace::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	/// The disputes are resolved from the super user
+	type ArbitratorOrigin = frame_system::EnsureRoot<AccountId>;
+	/// The curators of the reference data are managed from the super user
+	type CuratorOrigin = frame_system::EnsureRoot<AccountId>;
+	/// The sellers and the products reserve a deposit for the data stored
+	type ItemDeposit = ConstU128<{ deposit(1, 0) }>;
+	type ByteDeposit = ConstU128<{ deposit(0, 1) }>;
+	/// The currencies are settled with the assets of pallet-assets
+	type AssetId = u32;
+	type Assets = Assets;
+	/// The tax rates are managed from the super user
+	type TaxOrigin = frame_system::EnsureRoot<AccountId>;
+	/// The invoices are anchored in the documents of pallet-docsig
+	type DocumentAnchor = DocSigAnchor;
+	/// The resellers are set from the super user
+	type ResellerOrigin = frame_system::EnsureRoot<AccountId>;
+	/// The return policies of the sellers are in days
+	type BlocksPerDay = ConstU32<DAYS>;
+	/// The catalogues are imported in batches of 100 products
+	type MaxProductsBatch = ConstU32<100>;
+	/// The auctions are settled and the subscriptions charged with up to 10% of the block weight
+	type MaxInitializeWeight = MarketPlaceMaxInitializeWeight;
+}
+
+/// Anchor the hashes of the market place invoices as documents of pallet-docsig, so the parties
+/// can co-sign them with `sign_document`
+pub struct DocSigAnchor;
+impl pallet_marketplace::DocumentAnchor<AccountId> for DocSigAnchor {
+	fn anchor(owner: &AccountId, id: u32, hash: Vec<u8>) -> sp_runtime::DispatchResult {
+		DocSig::do_new_document(owner.clone(), id, hash)
+	}
 }
 
 /// Configure the pallet-docsig
@@ -313,6 +379,7 @@ construct_runtime!(
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		// Include the custom logic
@@ -347,6 +414,14 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_marketplace::migrations::v1::MigrateToV1<Runtime>,
+	pallet_marketplace::migrations::v2::MigrateToV2<Runtime>,
+	pallet_marketplace::migrations::v3::MigrateToV3<Runtime>,
+	pallet_marketplace::migrations::v4::MigrateToV4<Runtime>,
+	pallet_marketplace::migrations::v5::MigrateToV5<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -354,6 +429,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -366,6 +442,7 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
 		[pallet_timestamp, Timestamp]
 		[pallet_docsig, DocSig]
 
@@ -517,6 +594,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_marketplace_rpc_runtime_api::MarketplaceApi<Block, AccountId> for Runtime {
+		fn seller(account: AccountId) -> Option<pallet_marketplace::SellerInfo<AccountId>> {
+			MarketPlace::query_seller(account)
+		}
+		fn products(
+			filter: pallet_marketplace::ProductFilter<AccountId>,
+			start: u32,
+			limit: u32,
+		) -> Vec<(u32, pallet_marketplace::ProductInfo)> {
+			MarketPlace::query_products(filter, start, limit)
+		}
+		fn shipping_rates(shipper: u32) -> Vec<(u32, pallet_marketplace::ShippingRateTable)> {
+			MarketPlace::query_shipping_rates(shipper)
+		}
+		fn currencies(
+		) -> Vec<(pallet_marketplace::CurrencyCode, pallet_marketplace::CurrencyInfo)> {
+			MarketPlace::query_currencies()
+		}
+		fn quote_shipping(
+			uid: u32,
+			destination: Vec<u8>,
+			quantity: u32,
+		) -> Vec<(u32, Vec<u8>, pallet_marketplace::Balance)> {
+			MarketPlace::quote_shipping(uid, destination, quantity)
+		}
+		fn quote_tax(uid: u32, destination: Vec<u8>) -> Option<pallet_marketplace::TaxQuote> {
+			MarketPlace::quote_tax(uid, destination)
+		}
+		fn seller_ships_to(account: AccountId, latitude: i32, longitude: i32) -> bool {
+			MarketPlace::seller_ships_to(account, latitude, longitude)
+		}
+		fn product_ships_to(uid: u32, latitude: i32, longitude: i32) -> bool {
+			MarketPlace::product_ships_to(uid, latitude, longitude)
+		}
+		fn products_by_gtin(code: Vec<u8>) -> Vec<u32> {
+			MarketPlace::query_products_by_gtin(code)
+		}
+		fn seller_verification(
+			account: AccountId,
+		) -> Option<pallet_marketplace::SellerVerification> {
+			MarketPlace::query_seller_verification(account)
+		}
+		fn product_status(uid: u32) -> Option<pallet_marketplace::ProductStatus> {
+			MarketPlace::query_product_status(uid)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{